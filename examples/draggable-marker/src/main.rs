@@ -21,17 +21,15 @@ impl MapEventListener for Listener {
 }
 
 impl MarkerEventListener for MarkerListener {
-    fn on_dragend(&mut self, m: Rc<Marker>, _e: mapboxgl::event::DragEvent) {
+    fn on_dragend(&mut self, _m: Rc<Marker>, e: mapboxgl::event::MarkerDragEvent) {
         let document: Document = web_sys::window().unwrap().document().unwrap();
         let coordinates: Element = document.get_element_by_id("coordinates").unwrap();
         coordinates
             .set_attribute("style", "display: block;")
             .unwrap();
-        let lnglat = m.get_lnglat();
         coordinates.set_inner_html(&format!(
             "Longitude: {}<br/>Latitude: {}",
-            lnglat.lng(),
-            lnglat.lat()
+            e.lng_lat.lng, e.lng_lat.lat
         ));
     }
 }
@@ -1,5 +1,5 @@
 use mapboxgl::layer::{IntoLayer, Layer, RasterLayer};
-use mapboxgl::style::Sources;
+use mapboxgl::style::{RasterSourceSpec, Sources};
 use mapboxgl::{LngLat, Map, MapOptions, Source, Style};
 use std::{cell::RefCell, rc::Rc};
 use yew::prelude::*;
@@ -42,8 +42,7 @@ pub fn create_map() -> Rc<Map> {
     let mut sources = Sources::new();
     sources.insert(
         "carto-dark".into(),
-        Source {
-            r#type: "raster".into(),
+        Source::Raster(RasterSourceSpec {
             tiles: Some(vec![
                 "http://a.basemaps.cartocdn.com/dark_all/{z}/{x}/{y}.png".into(),
                 "http://b.basemaps.cartocdn.com/dark_all/{z}/{x}/{y}.png".into(),
@@ -51,7 +50,7 @@ pub fn create_map() -> Rc<Map> {
                 "http://d.basemaps.cartocdn.com/dark_all/{z}/{x}/{y}.png".into(),
             ]),
             ..Default::default()
-        },
+        }),
     );
     let layers: Vec<Layer> = vec![RasterLayer {
         id: "carto-dark-layer".into(),
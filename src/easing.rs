@@ -0,0 +1,118 @@
+//! Easing functions for camera animations.
+//!
+//! https://docs.mapbox.com/mapbox-gl-js/api/properties/#animationoptions
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+pub(crate) type EasingClosure = Closure<dyn Fn(f64) -> f64>;
+
+/// Maps the animation progress in `[0, 1]` to the eased progress.
+#[derive(Clone)]
+pub enum Easing {
+    Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+    EaseInCubic,
+    EaseOutCubic,
+    EaseInOutCubic,
+    EaseInSine,
+    EaseOutSine,
+    EaseInOutSine,
+    Custom(Rc<dyn Fn(f64) -> f64>),
+}
+
+impl Easing {
+    pub fn custom<F: Fn(f64) -> f64 + 'static>(f: F) -> Easing {
+        Easing::Custom(Rc::new(f))
+    }
+
+    pub fn apply(&self, t: f64) -> f64 {
+        use std::f64::consts::PI;
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::EaseInCubic => t * t * t,
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::EaseInSine => 1.0 - (t * PI / 2.0).cos(),
+            Easing::EaseOutSine => (t * PI / 2.0).sin(),
+            Easing::EaseInOutSine => -((t * PI).cos() - 1.0) / 2.0,
+            Easing::Custom(f) => f(t),
+        }
+    }
+
+    pub(crate) fn to_closure(&self) -> EasingClosure {
+        let easing = self.clone();
+        Closure::new(move |t: f64| easing.apply(t))
+    }
+}
+
+impl std::fmt::Debug for Easing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Easing::Linear => write!(f, "Linear"),
+            Easing::EaseInQuad => write!(f, "EaseInQuad"),
+            Easing::EaseOutQuad => write!(f, "EaseOutQuad"),
+            Easing::EaseInOutQuad => write!(f, "EaseInOutQuad"),
+            Easing::EaseInCubic => write!(f, "EaseInCubic"),
+            Easing::EaseOutCubic => write!(f, "EaseOutCubic"),
+            Easing::EaseInOutCubic => write!(f, "EaseInOutCubic"),
+            Easing::EaseInSine => write!(f, "EaseInSine"),
+            Easing::EaseOutSine => write!(f, "EaseOutSine"),
+            Easing::EaseInOutSine => write!(f, "EaseInOutSine"),
+            Easing::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        let half_sine = 1.0 - std::f64::consts::FRAC_1_SQRT_2;
+        for (easing, half) in [
+            (Easing::Linear, 0.5),
+            (Easing::EaseInQuad, 0.25),
+            (Easing::EaseOutQuad, 0.75),
+            (Easing::EaseInOutQuad, 0.5),
+            (Easing::EaseInCubic, 0.125),
+            (Easing::EaseOutCubic, 0.875),
+            (Easing::EaseInOutCubic, 0.5),
+            (Easing::EaseInSine, half_sine),
+            (Easing::EaseOutSine, 1.0 - half_sine),
+            (Easing::EaseInOutSine, 0.5),
+        ] {
+            for (t, expected) in [(0.0, 0.0), (0.5, half), (1.0, 1.0)] {
+                let actual = easing.apply(t);
+                assert!(
+                    (actual - expected).abs() < 1e-12,
+                    "{easing:?}({t}) = {actual}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom() {
+        let easing = Easing::custom(|t| t.sqrt());
+        assert_eq!(easing.apply(0.25), 0.5);
+    }
+}
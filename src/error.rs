@@ -8,6 +8,16 @@ pub enum Error {
     BadGeoJson(String),
     #[error("The object is not compatible to {0}: {1}")]
     BadEventFormat(&'static str, String),
+    #[error("Invalid expression: {0}")]
+    InvalidExpression(#[from] crate::expr::ValidationError),
+    /// Error reported by mapbox-gl-js through the `error` event
+    #[error("Map error: {message}")]
+    Map {
+        message: String,
+        status: Option<u16>,
+        url: Option<String>,
+        source_id: Option<String>,
+    },
     #[error("Error: {0}")]
     Unexpected(String),
     /// Error from Js/Rust conversions
@@ -1,6 +1,12 @@
-use crate::{Error, Result};
+use crate::{feature, Error, Map, MapListenerId, RenderedFeature, Result};
+use futures::{channel::mpsc, Stream};
 use log::*;
 use serde::{Deserialize, Serialize};
+use std::{
+    pin::Pin,
+    rc::Weak,
+    task::{Context, Poll},
+};
 use wasm_bindgen::prelude::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,10 +38,41 @@ impl TryFrom<JsValue> for MapBaseEvent {
     }
 }
 
+/// Movement event: `move*`, `drag*`, `zoom*`, `rotate*` and `pitch*`.
 #[derive(Debug, Clone)]
 pub struct MapEvent {
     pub r#type: String,
-    pub original_event: Option<web_sys::MouseEvent>,
+    /// DOM event that caused the map event, `None` when triggered programmatically, e.g. by
+    /// `fly_to`.
+    pub original_event: Option<web_sys::Event>,
+}
+
+impl MapEvent {
+    /// Whether the event was caused by the user interacting with the map.
+    pub fn is_user_initiated(&self) -> bool {
+        self.original_event.is_some()
+    }
+
+    /// Whether the event was caused by a camera method such as `fly_to` or `jump_to`.
+    pub fn is_programmatic(&self) -> bool {
+        self.original_event.is_none()
+    }
+
+    pub fn mouse_event(&self) -> Option<&web_sys::MouseEvent> {
+        self.original_event.as_ref()?.dyn_ref()
+    }
+
+    pub fn touch_event(&self) -> Option<&web_sys::TouchEvent> {
+        self.original_event.as_ref()?.dyn_ref()
+    }
+
+    pub fn wheel_event(&self) -> Option<&web_sys::WheelEvent> {
+        self.original_event.as_ref()?.dyn_ref()
+    }
+
+    pub fn keyboard_event(&self) -> Option<&web_sys::KeyboardEvent> {
+        self.original_event.as_ref()?.dyn_ref()
+    }
 }
 
 impl TryFrom<JsValue> for MapEvent {
@@ -49,28 +86,88 @@ impl TryFrom<JsValue> for MapEvent {
 
         Ok(MapEvent {
             r#type,
-            original_event: Some(web_sys::MouseEvent::from(event)),
+            original_event: event.dyn_into().ok(),
         })
     }
 }
 
+/// https://docs.mapbox.com/mapbox-gl-js/api/map/#map.event:styleimagemissing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StyleImageMissingEvent {
+    pub r#type: String,
+    /// ID of the missing image.
+    pub id: String,
+}
+
+impl TryFrom<JsValue> for StyleImageMissingEvent {
+    type Error = Error;
+
+    fn try_from(value: JsValue) -> Result<Self> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanonicalTileId {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TileId {
+    pub canonical: CanonicalTileId,
+    pub overscaled_z: u32,
+    pub wrap: i32,
+}
+
+/// Tile being loaded or changed in a data event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTile {
+    #[serde(rename = "tileID")]
+    pub tile_id: TileId,
+    pub state: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MapDataEvent {
     pub r#type: String,
     pub data_type: String,
     pub is_source_loaded: Option<bool>,
-    // TODO source
+    /// Style spec of the source for source events, kept as is so inline GeoJSON isn't
+    /// converted on every event. See [`MapDataEvent::source_spec`].
+    #[serde(skip)]
+    pub source: Option<JsValue>,
     pub source_data_type: Option<String>,
     pub source_id: Option<String>,
-    // TODO tile
+    pub tile: Option<DataTile>,
+    pub coord: Option<TileId>,
+}
+
+impl MapDataEvent {
+    /// Deserializes the style spec of the source, e.g. into [`Source`](crate::Source).
+    pub fn source_spec<T: serde::de::DeserializeOwned>(&self) -> Result<Option<T>> {
+        match &self.source {
+            Some(source) => Ok(Some(serde_wasm_bindgen::from_value(source.clone())?)),
+            None => Ok(None),
+        }
+    }
 }
 
 impl TryFrom<JsValue> for MapDataEvent {
     type Error = Error;
 
     fn try_from(value: JsValue) -> Result<Self> {
-        Ok(serde_wasm_bindgen::from_value(value)?)
+        let source = js_sys::Reflect::get(&value, &JsValue::from("source"))
+            .ok()
+            .filter(|s| !s.is_undefined() && !s.is_null());
+        let mut event: MapDataEvent = serde_wasm_bindgen::from_value(value)?;
+        event.source = source;
+        Ok(event)
     }
 }
 
@@ -102,7 +199,8 @@ pub struct MapMouseEvent {
     pub original_event: web_sys::MouseEvent,
     pub point: Point,
     pub lng_lat: LngLat,
-    pub features: Vec<String>,
+    /// Features under the pointer, only set for handlers registered with `on_layer`.
+    pub features: Vec<RenderedFeature>,
 }
 
 impl TryFrom<JsValue> for MapMouseEvent {
@@ -122,7 +220,7 @@ impl TryFrom<JsValue> for MapMouseEvent {
             original_event: web_sys::MouseEvent::from(event),
             point: serde_wasm_bindgen::from_value(point)?,
             lng_lat: serde_wasm_bindgen::from_value(lng_lat)?,
-            features: serde_wasm_bindgen::from_value(features).unwrap_or_default(),
+            features: feature::rendered_features_from_js_lossy(features),
         })
     }
 }
@@ -135,7 +233,8 @@ pub struct MapTouchEvent {
     pub points: Vec<Point>,
     pub lng_lat: LngLat,
     pub lng_lats: Vec<LngLat>,
-    pub features: Vec<String>,
+    /// Features under the pointer, only set for handlers registered with `on_layer`.
+    pub features: Vec<RenderedFeature>,
 }
 
 impl TryFrom<JsValue> for MapTouchEvent {
@@ -159,7 +258,7 @@ impl TryFrom<JsValue> for MapTouchEvent {
             points: serde_wasm_bindgen::from_value(points)?,
             lng_lat: serde_wasm_bindgen::from_value(lng_lat)?,
             lng_lats: serde_wasm_bindgen::from_value(lng_lats)?,
-            features: serde_wasm_bindgen::from_value(features).unwrap_or_default(),
+            features: feature::rendered_features_from_js_lossy(features),
         })
     }
 }
@@ -186,23 +285,230 @@ impl TryFrom<JsValue> for MapWheelEvent {
     }
 }
 
+/// Drag event of a draggable [`Marker`](crate::Marker).
+#[derive(Debug, Clone)]
+pub struct MarkerDragEvent {
+    pub r#type: String,
+    /// Position of the marker after the event.
+    pub lng_lat: LngLat,
+}
+
+impl TryFrom<JsValue> for MarkerDragEvent {
+    type Error = Error;
+
+    fn try_from(value: JsValue) -> Result<Self> {
+        let r#type = get_property(&value, "MarkerDragEvent", "type")?
+            .as_string()
+            .unwrap();
+        let target: crate::js::Marker =
+            get_property(&value, "MarkerDragEvent", "target")?.unchecked_into();
+        let lng_lat = target.getLngLat();
+
+        Ok(MarkerDragEvent {
+            r#type,
+            lng_lat: LngLat {
+                lng: lng_lat.lng(),
+                lat: lng_lat.lat(),
+            },
+        })
+    }
+}
+
+/// DOM mouse event on the element of a [`Marker`](crate::Marker).
 #[derive(Debug, Clone)]
-pub struct DragEvent {
-    pub original_event: web_sys::DragEvent,
+pub struct MarkerMouseEvent {
+    pub r#type: String,
+    pub original_event: web_sys::MouseEvent,
 }
 
-impl TryFrom<JsValue> for DragEvent {
+impl TryFrom<JsValue> for MarkerMouseEvent {
     type Error = Error;
 
     fn try_from(value: JsValue) -> Result<Self> {
-        let event = get_property(&value, "DragEvent", "originalEvent")?;
+        let original_event: web_sys::MouseEvent = value.dyn_into().map_err(|v| {
+            Error::BadEventFormat("MarkerMouseEvent", format!("not a MouseEvent: {v:?}"))
+        })?;
 
-        Ok(DragEvent {
-            original_event: web_sys::DragEvent::from(event),
+        Ok(MarkerMouseEvent {
+            r#type: original_event.type_(),
+            original_event,
         })
     }
 }
 
+/// Error reported by the map, e.g. a tile or style that failed to load.
+///
+/// https://docs.mapbox.com/mapbox-gl-js/api/map/#map.event:error
+#[derive(Debug, Clone)]
+pub struct MapErrorEvent {
+    pub r#type: String,
+    pub message: String,
+    /// HTTP status of a failed request.
+    pub status: Option<u16>,
+    /// URL of a failed request.
+    pub url: Option<String>,
+    /// Source the error is related to.
+    pub source_id: Option<String>,
+    /// The event as delivered by mapbox-gl-js.
+    pub raw: JsValue,
+}
+
+impl TryFrom<JsValue> for MapErrorEvent {
+    type Error = Error;
+
+    fn try_from(value: JsValue) -> Result<Self> {
+        let r#type = get_property(&value, "MapErrorEvent", "type")?
+            .as_string()
+            .unwrap_or_else(|| "error".into());
+        let error = get_property(&value, "MapErrorEvent", "error")?;
+        let message = match error.dyn_ref::<js_sys::Error>() {
+            Some(e) => e.message().into(),
+            None => error.as_string().unwrap_or_else(|| format!("{error:?}")),
+        };
+        let status = get_property(&error, "MapErrorEvent", "status")
+            .ok()
+            .and_then(|s| s.as_f64())
+            .map(|s| s as u16);
+        let url = get_property(&error, "MapErrorEvent", "url")
+            .ok()
+            .and_then(|u| u.as_string());
+        let source_id = get_property(&value, "MapErrorEvent", "sourceId")?.as_string();
+
+        Ok(MapErrorEvent {
+            r#type,
+            message,
+            status,
+            url,
+            source_id,
+            raw: value,
+        })
+    }
+}
+
+impl From<&MapErrorEvent> for Error {
+    fn from(e: &MapErrorEvent) -> Self {
+        Error::Map {
+            message: e.message.clone(),
+            status: e.status,
+            url: e.url.clone(),
+            source_id: e.source_id.clone(),
+        }
+    }
+}
+
+/// Kind of event that can be subscribed to on its own with
+/// [`Map::on_event`](crate::Map::on_event) or [`Map::once_event`](crate::Map::once_event).
+pub trait EventKind {
+    /// Name of the event in mapbox-gl-js.
+    const NAME: &'static str;
+    /// Payload passed to the listener.
+    type Event: TryFrom<JsValue, Error = Error> + 'static;
+}
+
+macro_rules! define_event_kinds {
+    ($(($kind:ident, $name:literal, $type:ident);)*) => {
+        /// Event kinds for typed single-event subscriptions, e.g. `map.on_event::<kind::Click>(..)`.
+        pub mod kind {
+            $(
+                #[doc = concat!("The `", $name, "` event.")]
+                #[derive(Debug, Clone, Copy, Default)]
+                pub struct $kind;
+
+                impl super::EventKind for $kind {
+                    const NAME: &'static str = $name;
+                    type Event = super::$type;
+                }
+            )*
+        }
+    };
+}
+
+define_event_kinds! {
+    (Resize, "resize", MapBaseEvent);
+    (Remove, "remove", MapBaseEvent);
+
+    // Interaction
+    (MouseDown, "mousedown", MapMouseEvent);
+    (MouseUp, "mouseup", MapMouseEvent);
+    (PreClick, "preclick", MapMouseEvent);
+    (Click, "click", MapMouseEvent);
+    (DblClick, "dblclick", MapMouseEvent);
+    (MouseMove, "mousemove", MapMouseEvent);
+    (MouseOver, "mouseover", MapMouseEvent);
+    (MouseEnter, "mouseenter", MapMouseEvent);
+    (MouseLeave, "mouseleave", MapMouseEvent);
+    (MouseOut, "mouseout", MapMouseEvent);
+    (ContextMenu, "contextmenu", MapMouseEvent);
+    (TouchStart, "touchstart", MapTouchEvent);
+    (TouchEnd, "touchend", MapTouchEvent);
+    (TouchCancel, "touchcancel", MapTouchEvent);
+    (Wheel, "wheel", MapWheelEvent);
+
+    // Movement
+    (MoveStart, "movestart", MapEvent);
+    (Move, "move", MapEvent);
+    (MoveEnd, "moveend", MapEvent);
+    (DragStart, "dragstart", MapEvent);
+    (Drag, "drag", MapEvent);
+    (DragEnd, "dragend", MapEvent);
+    (ZoomStart, "zoomstart", MapEvent);
+    (Zoom, "zoom", MapEvent);
+    (ZoomEnd, "zoomend", MapEvent);
+    (RotateStart, "rotatestart", MapEvent);
+    (Rotate, "rotate", MapEvent);
+    (RotateEnd, "rotateend", MapEvent);
+    (PitchStart, "pitchstart", MapEvent);
+    (Pitch, "pitch", MapEvent);
+    (PitchEnd, "pitchend", MapEvent);
+    (BoxZoomStart, "boxzoomstart", MapBoxZoomEvent);
+    (BoxZoomEnd, "boxzoomend", MapBoxZoomEvent);
+    (BoxZoomCancel, "boxzoomcancel", MapBoxZoomEvent);
+
+    // Lifecycle
+    (Load, "load", MapBaseEvent);
+    (Render, "render", MapBaseEvent);
+    (Idle, "idle", MapBaseEvent);
+    (WebGlContextLost, "webglcontextlost", MapBaseEvent);
+    (WebGlContextRestored, "webglcontextrestored", MapBaseEvent);
+
+    // Data loading
+    (Data, "data", MapDataEvent);
+    (StyleData, "styledata", MapDataEvent);
+    (SourceData, "sourcedata", MapDataEvent);
+    (DataLoading, "dataloading", MapDataEvent);
+    (StyleDataLoading, "styledataloading", MapDataEvent);
+    (SourceDataLoading, "sourcedataloading", MapDataEvent);
+    (StyleImageMissing, "styleimagemissing", StyleImageMissingEvent);
+
+    (Error, "error", MapErrorEvent);
+}
+
+/// Stream of events returned by [`Map::events`](crate::Map::events). The listener is removed
+/// from the map when the stream is dropped.
+pub struct EventStream<E> {
+    pub(crate) receiver: mpsc::UnboundedReceiver<E>,
+    pub(crate) map: Weak<Map>,
+    pub(crate) id: MapListenerId,
+}
+
+impl<E> Stream for EventStream<E> {
+    type Item = E;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<E>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl<E> Drop for EventStream<E> {
+    fn drop(&mut self) {
+        if let Some(map) = self.map.upgrade() {
+            if let Err(e) = map.off(self.id) {
+                warn!("Failed to remove event stream listener: {e}");
+            }
+        }
+    }
+}
+
 fn get_property(
     value: &JsValue,
     event_name: &'static str,
@@ -0,0 +1,830 @@
+//! Typed builders for mapbox-gl-js expressions.
+//!
+//! Every builder produces an [`Expr`] which serializes to the exact JSON array mapbox-gl-js
+//! expects, e.g. `expr::get("pop")` becomes `["get", "pop"]`. The type parameter of [`Expr`]
+//! tracks the return type of the expression, so a color can't be passed where a number is
+//! required. Use `.into()` to turn an [`Expr`] into a [`layer::Expression`](crate::layer::Expression)
+//! for paint and layout properties.
+//!
+//! See https://docs.mapbox.com/style-spec/reference/expressions
+mod validate;
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{Serialize, Serializer};
+
+use crate::layer::Expression;
+pub use validate::{validate, validate_as, Arity, ExpressionPath, Type, ValidationError};
+
+/// Expression returning a number.
+#[derive(Debug, Clone, Copy)]
+pub struct Number;
+
+/// Expression returning a string.
+#[derive(Debug, Clone, Copy)]
+pub struct Str;
+
+/// Expression returning a boolean.
+#[derive(Debug, Clone, Copy)]
+pub struct Boolean;
+
+/// Expression returning a color.
+#[derive(Debug, Clone, Copy)]
+pub struct Color;
+
+/// Expression returning an array.
+#[derive(Debug, Clone, Copy)]
+pub struct Array;
+
+/// Expression whose type is only known at runtime, e.g. `["get", "name"]`.
+#[derive(Debug, Clone, Copy)]
+pub struct Value;
+
+/// Return types that can be used as outputs of `interpolate`.
+pub trait Interpolatable {}
+impl Interpolatable for Number {}
+impl Interpolatable for Color {}
+impl Interpolatable for Array {}
+
+/// Uninhabited enum value, builders never produce `Expression::Enum`.
+#[derive(Debug, Clone, Serialize)]
+enum Never {}
+
+/// Expression with the return type `R`.
+#[derive(Debug, Clone)]
+pub struct Expr<R> {
+    inner: Expression<Never>,
+    ty: PhantomData<R>,
+}
+
+impl<R> Expr<R> {
+    fn new(inner: Expression<Never>) -> Expr<R> {
+        Expr {
+            inner,
+            ty: PhantomData,
+        }
+    }
+
+    fn op(name: &'static str, args: impl IntoIterator<Item = Expression<Never>>) -> Expr<R> {
+        let mut list = vec![Expression::String(name.into())];
+        list.extend(args);
+        Expr::new(Expression::List(list))
+    }
+
+    /// Forget the return type, e.g. to mix differently typed values in `coalesce`.
+    pub fn as_value(self) -> Expr<Value> {
+        Expr::new(self.inner)
+    }
+
+    pub fn into_expression<T>(self) -> Expression<T> {
+        retype(self.inner)
+    }
+}
+
+impl<R> Serialize for Expr<R> {
+    fn serialize<S>(&self, ser: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(ser)
+    }
+}
+
+impl<R> fmt::Display for Expr<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl<R, T> From<Expr<R>> for Expression<T> {
+    fn from(expr: Expr<R>) -> Self {
+        expr.into_expression()
+    }
+}
+
+fn retype<T>(expr: Expression<Never>) -> Expression<T> {
+    match expr {
+        Expression::Enum(never) => match never {},
+        Expression::String(s) => Expression::String(s),
+        Expression::Number(n) => Expression::Number(n),
+        Expression::Bool(b) => Expression::Bool(b),
+        Expression::List(list) => Expression::List(list.into_iter().map(retype).collect()),
+        Expression::Object(map) => {
+            Expression::Object(map.into_iter().map(|(k, v)| (k, retype(v))).collect())
+        }
+    }
+}
+
+/// Values that can be used as an argument of an expression builder.
+pub trait IntoExpr {
+    type Output;
+
+    fn into_expr(self) -> Expr<Self::Output>;
+}
+
+impl<R> IntoExpr for Expr<R> {
+    type Output = R;
+
+    fn into_expr(self) -> Expr<R> {
+        self
+    }
+}
+
+impl IntoExpr for f64 {
+    type Output = Number;
+
+    fn into_expr(self) -> Expr<Number> {
+        Expr::new(Expression::Number(self))
+    }
+}
+
+impl IntoExpr for &str {
+    type Output = Str;
+
+    fn into_expr(self) -> Expr<Str> {
+        Expr::new(Expression::String(self.into()))
+    }
+}
+
+impl IntoExpr for String {
+    type Output = Str;
+
+    fn into_expr(self) -> Expr<Str> {
+        Expr::new(Expression::String(self))
+    }
+}
+
+impl IntoExpr for bool {
+    type Output = Boolean;
+
+    fn into_expr(self) -> Expr<Boolean> {
+        Expr::new(Expression::Bool(self))
+    }
+}
+
+fn arg(value: impl IntoExpr) -> Expression<Never> {
+    value.into_expr().inner
+}
+
+/// Label of a `match` arm, either a single literal or a list of literals.
+#[derive(Debug, Clone)]
+pub enum MatchLabel {
+    Number(f64),
+    String(String),
+    Numbers(Vec<f64>),
+    Strings(Vec<String>),
+}
+
+impl From<f64> for MatchLabel {
+    fn from(value: f64) -> Self {
+        MatchLabel::Number(value)
+    }
+}
+
+impl From<&str> for MatchLabel {
+    fn from(value: &str) -> Self {
+        MatchLabel::String(value.into())
+    }
+}
+
+impl From<String> for MatchLabel {
+    fn from(value: String) -> Self {
+        MatchLabel::String(value)
+    }
+}
+
+impl From<Vec<f64>> for MatchLabel {
+    fn from(value: Vec<f64>) -> Self {
+        MatchLabel::Numbers(value)
+    }
+}
+
+impl From<Vec<&str>> for MatchLabel {
+    fn from(value: Vec<&str>) -> Self {
+        MatchLabel::Strings(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> From<MatchLabel> for Expression<T> {
+    fn from(label: MatchLabel) -> Self {
+        match label {
+            MatchLabel::Number(n) => Expression::Number(n),
+            MatchLabel::String(s) => Expression::String(s),
+            MatchLabel::Numbers(ns) => {
+                Expression::List(ns.into_iter().map(Expression::Number).collect())
+            }
+            MatchLabel::Strings(ss) => {
+                Expression::List(ss.into_iter().map(Expression::String).collect())
+            }
+        }
+    }
+}
+
+// Types
+
+/// Element of a [`literal`] array.
+#[derive(Debug, Clone)]
+pub enum LiteralValue {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    List(Vec<LiteralValue>),
+}
+
+impl From<f64> for LiteralValue {
+    fn from(value: f64) -> Self {
+        LiteralValue::Number(value)
+    }
+}
+
+impl From<&str> for LiteralValue {
+    fn from(value: &str) -> Self {
+        LiteralValue::String(value.into())
+    }
+}
+
+impl From<String> for LiteralValue {
+    fn from(value: String) -> Self {
+        LiteralValue::String(value)
+    }
+}
+
+impl From<bool> for LiteralValue {
+    fn from(value: bool) -> Self {
+        LiteralValue::Bool(value)
+    }
+}
+
+impl<V: Into<LiteralValue>> From<Vec<V>> for LiteralValue {
+    fn from(values: Vec<V>) -> Self {
+        LiteralValue::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<LiteralValue> for Expression<Never> {
+    fn from(value: LiteralValue) -> Self {
+        match value {
+            LiteralValue::Number(n) => Expression::Number(n),
+            LiteralValue::String(s) => Expression::String(s),
+            LiteralValue::Bool(b) => Expression::Bool(b),
+            LiteralValue::List(list) => {
+                Expression::List(list.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+/// `["literal", [...]]`
+pub fn literal<V: Into<LiteralValue>>(values: impl IntoIterator<Item = V>) -> Expr<Array> {
+    Expr::op(
+        "literal",
+        [Expression::List(
+            values.into_iter().map(|v| v.into().into()).collect(),
+        )],
+    )
+}
+
+/// A color literal such as `"#ff0000"` or `"rgba(0, 0, 0, 0.5)"`.
+pub fn color(value: impl Into<String>) -> Expr<Color> {
+    Expr::new(Expression::String(value.into()))
+}
+
+/// `["number", value]`
+pub fn number(value: impl IntoExpr) -> Expr<Number> {
+    Expr::op("number", [arg(value)])
+}
+
+/// `["string", value]`
+pub fn string(value: impl IntoExpr) -> Expr<Str> {
+    Expr::op("string", [arg(value)])
+}
+
+/// `["boolean", value]`
+pub fn boolean(value: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("boolean", [arg(value)])
+}
+
+/// `["to-number", value]`
+pub fn to_number(value: impl IntoExpr) -> Expr<Number> {
+    Expr::op("to-number", [arg(value)])
+}
+
+/// `["to-string", value]`
+pub fn to_string(value: impl IntoExpr) -> Expr<Str> {
+    Expr::op("to-string", [arg(value)])
+}
+
+/// `["to-boolean", value]`
+pub fn to_boolean(value: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("to-boolean", [arg(value)])
+}
+
+/// `["to-color", value]`
+pub fn to_color(value: impl IntoExpr) -> Expr<Color> {
+    Expr::op("to-color", [arg(value)])
+}
+
+// Feature data
+
+/// `["get", property]`
+pub fn get(property: impl Into<String>) -> Expr<Value> {
+    Expr::op("get", [Expression::String(property.into())])
+}
+
+/// `["get", property, object]`
+pub fn get_in(property: impl Into<String>, object: Expr<Value>) -> Expr<Value> {
+    Expr::op("get", [Expression::String(property.into()), object.inner])
+}
+
+/// `["has", property]`
+pub fn has(property: impl Into<String>) -> Expr<Boolean> {
+    Expr::op("has", [Expression::String(property.into())])
+}
+
+/// `["id"]`
+pub fn id() -> Expr<Value> {
+    Expr::op("id", [])
+}
+
+/// `["geometry-type"]`
+pub fn geometry_type() -> Expr<Str> {
+    Expr::op("geometry-type", [])
+}
+
+/// `["properties"]`
+pub fn properties() -> Expr<Value> {
+    Expr::op("properties", [])
+}
+
+/// `["feature-state", key]`
+pub fn feature_state(key: impl Into<String>) -> Expr<Value> {
+    Expr::op("feature-state", [Expression::String(key.into())])
+}
+
+/// `["accumulated"]`
+pub fn accumulated() -> Expr<Value> {
+    Expr::op("accumulated", [])
+}
+
+// Lookup
+
+/// `["at", index, array]`
+pub fn at(
+    index: impl IntoExpr<Output = Number>,
+    array: impl IntoExpr<Output = Array>,
+) -> Expr<Value> {
+    Expr::op("at", [arg(index), arg(array)])
+}
+
+/// `["in", needle, haystack]`
+pub fn in_(needle: impl IntoExpr, haystack: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("in", [arg(needle), arg(haystack)])
+}
+
+/// `["length", value]`
+pub fn length(value: impl IntoExpr) -> Expr<Number> {
+    Expr::op("length", [arg(value)])
+}
+
+// Camera and rendering
+
+/// `["zoom"]`
+pub fn zoom() -> Expr<Number> {
+    Expr::op("zoom", [])
+}
+
+/// `["pitch"]`
+pub fn pitch() -> Expr<Number> {
+    Expr::op("pitch", [])
+}
+
+/// `["distance-from-center"]`
+pub fn distance_from_center() -> Expr<Number> {
+    Expr::op("distance-from-center", [])
+}
+
+/// `["heatmap-density"]`
+pub fn heatmap_density() -> Expr<Number> {
+    Expr::op("heatmap-density", [])
+}
+
+/// `["line-progress"]`
+pub fn line_progress() -> Expr<Number> {
+    Expr::op("line-progress", [])
+}
+
+// Decision
+
+/// `["!", value]`
+pub fn not(value: impl IntoExpr<Output = Boolean>) -> Expr<Boolean> {
+    Expr::op("!", [arg(value)])
+}
+
+/// `["all", ...]`
+pub fn all<E: IntoExpr<Output = Boolean>>(values: impl IntoIterator<Item = E>) -> Expr<Boolean> {
+    Expr::op("all", values.into_iter().map(arg))
+}
+
+/// `["any", ...]`
+pub fn any<E: IntoExpr<Output = Boolean>>(values: impl IntoIterator<Item = E>) -> Expr<Boolean> {
+    Expr::op("any", values.into_iter().map(arg))
+}
+
+/// `["==", lhs, rhs]`
+pub fn eq(lhs: impl IntoExpr, rhs: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("==", [arg(lhs), arg(rhs)])
+}
+
+/// `["!=", lhs, rhs]`
+pub fn ne(lhs: impl IntoExpr, rhs: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("!=", [arg(lhs), arg(rhs)])
+}
+
+/// `["<", lhs, rhs]`
+pub fn lt(lhs: impl IntoExpr, rhs: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("<", [arg(lhs), arg(rhs)])
+}
+
+/// `["<=", lhs, rhs]`
+pub fn le(lhs: impl IntoExpr, rhs: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op("<=", [arg(lhs), arg(rhs)])
+}
+
+/// `[">", lhs, rhs]`
+pub fn gt(lhs: impl IntoExpr, rhs: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op(">", [arg(lhs), arg(rhs)])
+}
+
+/// `[">=", lhs, rhs]`
+pub fn ge(lhs: impl IntoExpr, rhs: impl IntoExpr) -> Expr<Boolean> {
+    Expr::op(">=", [arg(lhs), arg(rhs)])
+}
+
+/// `["case", condition, output, ..., fallback]`
+///
+/// # Panics
+///
+/// Panics if `branches` is empty, mapbox-gl-js rejects a `case` without a condition.
+pub fn case<C, O>(
+    branches: impl IntoIterator<Item = (C, O)>,
+    fallback: impl IntoExpr<Output = O::Output>,
+) -> Expr<O::Output>
+where
+    C: IntoExpr<Output = Boolean>,
+    O: IntoExpr,
+{
+    let mut args = vec![];
+    for (condition, output) in branches {
+        args.push(arg(condition));
+        args.push(arg(output));
+    }
+    assert!(!args.is_empty(), "case needs at least one branch");
+    args.push(arg(fallback));
+    Expr::op("case", args)
+}
+
+/// `["match", input, label, output, ..., fallback]`
+///
+/// # Panics
+///
+/// Panics if `arms` is empty, mapbox-gl-js rejects a `match` without a label.
+pub fn match_<L, O>(
+    input: impl IntoExpr,
+    arms: impl IntoIterator<Item = (L, O)>,
+    fallback: impl IntoExpr<Output = O::Output>,
+) -> Expr<O::Output>
+where
+    L: Into<MatchLabel>,
+    O: IntoExpr,
+{
+    let mut args = vec![arg(input)];
+    for (label, output) in arms {
+        args.push(label.into().into());
+        args.push(arg(output));
+    }
+    assert!(args.len() > 1, "match needs at least one arm");
+    args.push(arg(fallback));
+    Expr::op("match", args)
+}
+
+/// `["coalesce", ...]`
+pub fn coalesce<E: IntoExpr>(values: impl IntoIterator<Item = E>) -> Expr<E::Output> {
+    Expr::op("coalesce", values.into_iter().map(arg))
+}
+
+// Ramps, scales, curves
+
+/// `["step", input, output, stop, output, ...]`
+pub fn step<O: IntoExpr>(
+    input: impl IntoExpr<Output = Number>,
+    base: O,
+    stops: impl IntoIterator<Item = (f64, O)>,
+) -> Expr<O::Output> {
+    let mut args = vec![arg(input), arg(base)];
+    for (stop, output) in stops {
+        args.push(Expression::Number(stop));
+        args.push(arg(output));
+    }
+    Expr::op("step", args)
+}
+
+fn interpolate<O>(
+    interpolation: Expression<Never>,
+    input: impl IntoExpr<Output = Number>,
+    stops: impl IntoIterator<Item = (f64, O)>,
+) -> Expr<O::Output>
+where
+    O: IntoExpr,
+    O::Output: Interpolatable,
+{
+    let mut args = vec![interpolation, arg(input)];
+    for (stop, output) in stops {
+        args.push(Expression::Number(stop));
+        args.push(arg(output));
+    }
+    Expr::op("interpolate", args)
+}
+
+/// `["interpolate", ["linear"], input, stop, output, ...]`
+pub fn interpolate_linear<O>(
+    input: impl IntoExpr<Output = Number>,
+    stops: impl IntoIterator<Item = (f64, O)>,
+) -> Expr<O::Output>
+where
+    O: IntoExpr,
+    O::Output: Interpolatable,
+{
+    interpolate(Expression::List(vec!["linear".into()]), input, stops)
+}
+
+/// `["interpolate", ["exponential", base], input, stop, output, ...]`
+pub fn interpolate_exponential<O>(
+    base: f64,
+    input: impl IntoExpr<Output = Number>,
+    stops: impl IntoIterator<Item = (f64, O)>,
+) -> Expr<O::Output>
+where
+    O: IntoExpr,
+    O::Output: Interpolatable,
+{
+    interpolate(
+        Expression::List(vec!["exponential".into(), base.into()]),
+        input,
+        stops,
+    )
+}
+
+/// `["interpolate", ["cubic-bezier", x1, y1, x2, y2], input, stop, output, ...]`
+pub fn interpolate_cubic_bezier<O>(
+    control_points: (f64, f64, f64, f64),
+    input: impl IntoExpr<Output = Number>,
+    stops: impl IntoIterator<Item = (f64, O)>,
+) -> Expr<O::Output>
+where
+    O: IntoExpr,
+    O::Output: Interpolatable,
+{
+    let (x1, y1, x2, y2) = control_points;
+    interpolate(
+        Expression::List(vec![
+            "cubic-bezier".into(),
+            x1.into(),
+            y1.into(),
+            x2.into(),
+            y2.into(),
+        ]),
+        input,
+        stops,
+    )
+}
+
+// Math
+
+/// `["+", lhs, rhs]`
+pub fn add(
+    lhs: impl IntoExpr<Output = Number>,
+    rhs: impl IntoExpr<Output = Number>,
+) -> Expr<Number> {
+    Expr::op("+", [arg(lhs), arg(rhs)])
+}
+
+/// `["-", lhs, rhs]`
+pub fn sub(
+    lhs: impl IntoExpr<Output = Number>,
+    rhs: impl IntoExpr<Output = Number>,
+) -> Expr<Number> {
+    Expr::op("-", [arg(lhs), arg(rhs)])
+}
+
+/// `["*", lhs, rhs]`
+pub fn mul(
+    lhs: impl IntoExpr<Output = Number>,
+    rhs: impl IntoExpr<Output = Number>,
+) -> Expr<Number> {
+    Expr::op("*", [arg(lhs), arg(rhs)])
+}
+
+/// `["/", lhs, rhs]`
+pub fn div(
+    lhs: impl IntoExpr<Output = Number>,
+    rhs: impl IntoExpr<Output = Number>,
+) -> Expr<Number> {
+    Expr::op("/", [arg(lhs), arg(rhs)])
+}
+
+/// `["%", lhs, rhs]`
+pub fn rem(
+    lhs: impl IntoExpr<Output = Number>,
+    rhs: impl IntoExpr<Output = Number>,
+) -> Expr<Number> {
+    Expr::op("%", [arg(lhs), arg(rhs)])
+}
+
+/// `["^", base, exponent]`
+pub fn pow(
+    base: impl IntoExpr<Output = Number>,
+    exponent: impl IntoExpr<Output = Number>,
+) -> Expr<Number> {
+    Expr::op("^", [arg(base), arg(exponent)])
+}
+
+/// `["min", ...]`
+pub fn min<E: IntoExpr<Output = Number>>(values: impl IntoIterator<Item = E>) -> Expr<Number> {
+    Expr::op("min", values.into_iter().map(arg))
+}
+
+/// `["max", ...]`
+pub fn max<E: IntoExpr<Output = Number>>(values: impl IntoIterator<Item = E>) -> Expr<Number> {
+    Expr::op("max", values.into_iter().map(arg))
+}
+
+macro_rules! unary_math {
+    ($(($name:ident, $op:literal),)*) => {
+        $(
+            #[doc = concat!("`[\"", $op, "\", value]`")]
+            pub fn $name(value: impl IntoExpr<Output = Number>) -> Expr<Number> {
+                Expr::op($op, [arg(value)])
+            }
+        )*
+    };
+}
+
+unary_math! {
+    (abs, "abs"),
+    (ceil, "ceil"),
+    (floor, "floor"),
+    (round, "round"),
+    (sqrt, "sqrt"),
+    (ln, "ln"),
+    (log10, "log10"),
+    (log2, "log2"),
+    (sin, "sin"),
+    (cos, "cos"),
+    (tan, "tan"),
+}
+
+/// `["pi"]`
+pub fn pi() -> Expr<Number> {
+    Expr::op("pi", [])
+}
+
+/// `["e"]`
+pub fn e() -> Expr<Number> {
+    Expr::op("e", [])
+}
+
+// String
+
+/// `["concat", ...]`
+pub fn concat<E: IntoExpr>(values: impl IntoIterator<Item = E>) -> Expr<Str> {
+    Expr::op("concat", values.into_iter().map(arg))
+}
+
+/// `["upcase", value]`
+pub fn upcase(value: impl IntoExpr<Output = Str>) -> Expr<Str> {
+    Expr::op("upcase", [arg(value)])
+}
+
+/// `["downcase", value]`
+pub fn downcase(value: impl IntoExpr<Output = Str>) -> Expr<Str> {
+    Expr::op("downcase", [arg(value)])
+}
+
+// Color
+
+/// `["rgb", red, green, blue]`
+pub fn rgb(
+    red: impl IntoExpr<Output = Number>,
+    green: impl IntoExpr<Output = Number>,
+    blue: impl IntoExpr<Output = Number>,
+) -> Expr<Color> {
+    Expr::op("rgb", [arg(red), arg(green), arg(blue)])
+}
+
+/// `["rgba", red, green, blue, alpha]`
+pub fn rgba(
+    red: impl IntoExpr<Output = Number>,
+    green: impl IntoExpr<Output = Number>,
+    blue: impl IntoExpr<Output = Number>,
+    alpha: impl IntoExpr<Output = Number>,
+) -> Expr<Color> {
+    Expr::op("rgba", [arg(red), arg(green), arg(blue), arg(alpha)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn to_json<R>(expr: Expr<R>) -> serde_json::Value {
+        serde_json::to_value(&expr).unwrap()
+    }
+
+    #[test]
+    fn get() {
+        assert_eq!(to_json(super::get("pop")), json!(["get", "pop"]));
+    }
+
+    #[test]
+    fn interpolate_linear() {
+        let expr = super::interpolate_linear(zoom(), [(5.0, 1.0), (10.0, 4.0)]);
+        assert_eq!(
+            to_json(expr),
+            json!(["interpolate", ["linear"], ["zoom"], 5.0, 1.0, 10.0, 4.0])
+        );
+    }
+
+    #[test]
+    fn match_and_case() {
+        let expr = match_(
+            super::get("type"),
+            [("park", color("#0f0")), ("water", color("#00f"))],
+            color("#fff"),
+        );
+        assert_eq!(
+            to_json(expr),
+            json!([
+                "match",
+                ["get", "type"],
+                "park",
+                "#0f0",
+                "water",
+                "#00f",
+                "#fff"
+            ])
+        );
+
+        let expr = match_(super::get("class"), [(vec!["a", "b"], 1.0)], 0.0);
+        assert_eq!(
+            to_json(expr),
+            json!(["match", ["get", "class"], ["a", "b"], 1.0, 0.0])
+        );
+
+        let expr = case([(has("name"), 1.0)], 0.5);
+        assert_eq!(to_json(expr), json!(["case", ["has", "name"], 1.0, 0.5]));
+    }
+
+    #[test]
+    #[should_panic(expected = "case needs at least one branch")]
+    fn empty_case() {
+        case(Vec::<(Expr<Boolean>, f64)>::new(), 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "match needs at least one arm")]
+    fn empty_match() {
+        match_(super::get("type"), Vec::<(&str, f64)>::new(), 0.0);
+    }
+
+    #[test]
+    fn coalesce() {
+        let expr = super::coalesce([super::get("name_en"), super::get("name")]);
+        assert_eq!(
+            to_json(expr),
+            json!(["coalesce", ["get", "name_en"], ["get", "name"]])
+        );
+    }
+
+    #[test]
+    fn literal() {
+        assert_eq!(
+            to_json(super::literal(["a", "b"])),
+            json!(["literal", ["a", "b"]])
+        );
+        assert_eq!(
+            to_json(super::literal([vec![1.0, 2.0], vec![3.0]])),
+            json!(["literal", [[1.0, 2.0], [3.0]]])
+        );
+    }
+
+    #[test]
+    fn object_order_is_deterministic() {
+        let object: Expression<()> = Expression::Object(
+            [("b", 2.0), ("a", 1.0), ("c", 3.0)]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), Expression::Number(v)))
+                .collect(),
+        );
+        assert_eq!(object.to_string(), r#"{"a": 1, "b": 2, "c": 3}"#);
+    }
+}
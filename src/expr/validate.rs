@@ -0,0 +1,894 @@
+//! Static validation of expressions against the style-spec operator table.
+//!
+//! This doesn't depend on mapbox-gl-js, so styles can be checked in plain `cargo test`.
+//! Legacy filter syntax (e.g. `["in", "class", "a", "b"]`) is not supported.
+//!
+//! See https://docs.mapbox.com/style-spec/reference/expressions
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use crate::{layer::Expression, Result};
+
+/// Type of a value produced by an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Number,
+    String,
+    Boolean,
+    Color,
+    Object,
+    Array,
+    Null,
+    Formatted,
+    ResolvedImage,
+    Collator,
+    /// Type only known at runtime, e.g. the result of `["get", "name"]`.
+    Value,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Type::Number => "number",
+            Type::String => "string",
+            Type::Boolean => "boolean",
+            Type::Color => "color",
+            Type::Object => "object",
+            Type::Array => "array",
+            Type::Null => "null",
+            Type::Formatted => "formatted",
+            Type::ResolvedImage => "resolvedImage",
+            Type::Collator => "collator",
+            Type::Value => "value",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Type {
+    /// Whether a value of type `found` can be used where `self` is expected.
+    fn accepts(self, found: Type) -> bool {
+        use Type::*;
+
+        self == found
+            || self == Value
+            || found == Value
+            || matches!((self, found), (Color | Formatted | ResolvedImage, String))
+    }
+}
+
+/// Location of a sub-expression, as indices into the nested arrays. Displayed as `$[2][1]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpressionPath(pub Vec<usize>);
+
+impl fmt::Display for ExpressionPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$")?;
+        for index in &self.0 {
+            write!(f, "[{index}]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of arguments accepted by an operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+    OneOf(Vec<usize>),
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exactly(n) => write!(f, "{n}"),
+            Arity::AtLeast(n) => write!(f, "at least {n}"),
+            Arity::OneOf(ns) => {
+                let ns: Vec<String> = ns.iter().map(|n| n.to_string()).collect();
+                write!(f, "{}", ns.join(" or "))
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    #[error("{path}: unknown expression operator \"{name}\"")]
+    UnknownOperator { path: ExpressionPath, name: String },
+    #[error("{path}: \"{operator}\" expects {expected} arguments, found {found}")]
+    WrongArity {
+        path: ExpressionPath,
+        operator: String,
+        expected: Arity,
+        found: usize,
+    },
+    #[error("{path}: expected {expected}, found {found}")]
+    TypeMismatch {
+        path: ExpressionPath,
+        expected: Type,
+        found: Type,
+    },
+    #[error("{path}: {message}")]
+    Invalid {
+        path: ExpressionPath,
+        message: String,
+    },
+}
+
+/// Validate an expression and return its type.
+///
+/// Arrays must be expressions, so a typo in an operator name is reported as
+/// [`ValidationError::UnknownOperator`]. Use [`validate_as`] with [`Type::Array`] for
+/// properties that take a constant array, e.g. `["Open Sans Regular"]` for `text-font`.
+pub fn validate<T>(expr: &Expression<T>) -> Result<Type> {
+    Ok(Checker::default().check(expr)?)
+}
+
+/// Validate an expression and check it produces a value of type `expected`.
+///
+/// When `expected` is [`Type::Array`], a top-level array of literals whose first element
+/// is not an operator is accepted as a constant array, the same way mapbox-gl-js does.
+pub fn validate_as<T>(expr: &Expression<T>, expected: Type) -> Result<()> {
+    if expected == Type::Array && is_constant_array(expr) {
+        return Ok(());
+    }
+
+    let found = validate(expr)?;
+    if expected.accepts(found) {
+        Ok(())
+    } else {
+        Err(ValidationError::TypeMismatch {
+            path: ExpressionPath::default(),
+            expected,
+            found,
+        }
+        .into())
+    }
+}
+
+fn is_constant_array<T>(expr: &Expression<T>) -> bool {
+    let Expression::List(list) = expr else {
+        return false;
+    };
+    let is_call = matches!(list.first(), Some(Expression::String(op)) if is_operator(op));
+    !is_call
+        && list.iter().all(|e| {
+            matches!(
+                e,
+                Expression::String(_) | Expression::Number(_) | Expression::Bool(_)
+            )
+        })
+}
+
+/// Parameters of an operator with a fixed signature.
+enum Params {
+    /// Overloads distinguished by the number of arguments.
+    Fixed(&'static [&'static [Type]]),
+    Variadic {
+        param: Type,
+        min: usize,
+    },
+}
+
+fn signature(op: &str) -> Option<(Params, Type)> {
+    use Params::*;
+    use Type::*;
+
+    let sig = match op {
+        // Types
+        "collator" => (Fixed(&[&[Object]]), Collator),
+        "image" => (Fixed(&[&[String]]), ResolvedImage),
+        "number" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            Number,
+        ),
+        "string" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            String,
+        ),
+        "boolean" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            Boolean,
+        ),
+        "object" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            Object,
+        ),
+        "number-format" => (Fixed(&[&[Number, Object]]), String),
+        "to-boolean" => (Fixed(&[&[Value]]), Boolean),
+        "to-color" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            Color,
+        ),
+        "to-number" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            Number,
+        ),
+        "to-string" => (Fixed(&[&[Value]]), String),
+        "typeof" => (Fixed(&[&[Value]]), String),
+        // Feature data
+        "accumulated" => (Fixed(&[&[]]), Value),
+        "feature-state" => (Fixed(&[&[String]]), Value),
+        "geometry-type" => (Fixed(&[&[]]), String),
+        "id" => (Fixed(&[&[]]), Value),
+        "line-progress" => (Fixed(&[&[]]), Number),
+        "properties" => (Fixed(&[&[]]), Object),
+        // Lookup
+        "at" => (Fixed(&[&[Number, Array]]), Value),
+        "config" => (Fixed(&[&[String], &[String, String]]), Value),
+        "get" => (Fixed(&[&[String], &[String, Object]]), Value),
+        "has" => (Fixed(&[&[String], &[String, Object]]), Boolean),
+        "in" => (Fixed(&[&[Value, Value]]), Boolean),
+        "index-of" => (Fixed(&[&[Value, Value], &[Value, Value, Number]]), Number),
+        "length" => (Fixed(&[&[Value]]), Number),
+        "measure-light" => (Fixed(&[&[String]]), Number),
+        "slice" => (Fixed(&[&[Value, Number], &[Value, Number, Number]]), Value),
+        // Decision
+        "!" => (Fixed(&[&[Boolean]]), Boolean),
+        "all" | "any" => (
+            Variadic {
+                param: Boolean,
+                min: 0,
+            },
+            Boolean,
+        ),
+        "within" => (Fixed(&[&[Object]]), Boolean),
+        // String
+        "concat" => (
+            Variadic {
+                param: Value,
+                min: 1,
+            },
+            String,
+        ),
+        "downcase" | "upcase" => (Fixed(&[&[String]]), String),
+        "is-supported-script" => (Fixed(&[&[String]]), Boolean),
+        "resolved-locale" => (Fixed(&[&[Collator]]), String),
+        // Color
+        "hsl" | "rgb" => (Fixed(&[&[Number, Number, Number]]), Color),
+        "hsla" | "rgba" => (Fixed(&[&[Number, Number, Number, Number]]), Color),
+        "to-rgba" => (Fixed(&[&[Color]]), Array),
+        // Math
+        "+" | "*" => (
+            Variadic {
+                param: Number,
+                min: 2,
+            },
+            Number,
+        ),
+        "-" => (Fixed(&[&[Number], &[Number, Number]]), Number),
+        "/" | "%" | "^" => (Fixed(&[&[Number, Number]]), Number),
+        "abs" | "acos" | "asin" | "atan" | "ceil" | "cos" | "floor" | "ln" | "log10" | "log2"
+        | "round" | "sin" | "sqrt" | "tan" => (Fixed(&[&[Number]]), Number),
+        "distance" => (Fixed(&[&[Object]]), Number),
+        "e" | "ln2" | "pi" => (Fixed(&[&[]]), Number),
+        "min" | "max" => (
+            Variadic {
+                param: Number,
+                min: 1,
+            },
+            Number,
+        ),
+        "random" => (Fixed(&[&[Number, Number, Value]]), Number),
+        // Camera
+        "distance-from-center" | "pitch" | "zoom" => (Fixed(&[&[]]), Number),
+        // Heatmap, raster and sky
+        "heatmap-density" | "raster-particle-speed" | "raster-value" | "sky-radial-progress" => {
+            (Fixed(&[&[]]), Number)
+        }
+        _ => return None,
+    };
+
+    Some(sig)
+}
+
+fn is_operator(op: &str) -> bool {
+    matches!(
+        op,
+        "literal"
+            | "array"
+            | "format"
+            | "let"
+            | "var"
+            | "case"
+            | "match"
+            | "coalesce"
+            | "step"
+            | "interpolate"
+            | "interpolate-hcl"
+            | "interpolate-lab"
+            | "=="
+            | "!="
+            | "<"
+            | "<="
+            | ">"
+            | ">="
+    ) || signature(op).is_some()
+}
+
+type Checked = std::result::Result<Type, ValidationError>;
+
+#[derive(Default)]
+struct Checker {
+    path: Vec<usize>,
+    scopes: Vec<HashMap<String, Type>>,
+}
+
+impl Checker {
+    fn path(&self) -> ExpressionPath {
+        ExpressionPath(self.path.clone())
+    }
+
+    fn invalid(&self, message: impl Into<String>) -> ValidationError {
+        ValidationError::Invalid {
+            path: self.path(),
+            message: message.into(),
+        }
+    }
+
+    fn check<T>(&mut self, expr: &Expression<T>) -> Checked {
+        match expr {
+            Expression::Enum(_) | Expression::String(_) => Ok(Type::String),
+            Expression::Number(_) => Ok(Type::Number),
+            Expression::Bool(_) => Ok(Type::Boolean),
+            Expression::Object(_) => Ok(Type::Object),
+            Expression::List(list) => match list.first() {
+                Some(Expression::String(op)) => self.check_call(op, &list[1..]),
+                _ => Err(self
+                    .invalid("expected an expression; use [\"literal\", [...]] for array values")),
+            },
+        }
+    }
+
+    fn mismatch(&self, expected: Type, found: Type) -> ValidationError {
+        ValidationError::TypeMismatch {
+            path: self.path(),
+            expected,
+            found,
+        }
+    }
+
+    /// Run `f` with the path pointing at the argument at `index` (0-based, excluding the operator).
+    fn at_arg<R>(&mut self, index: usize, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.path.push(index + 1);
+        let res = f(self);
+        self.path.pop();
+        res
+    }
+
+    fn check_arg<T>(&mut self, args: &[Expression<T>], index: usize) -> Checked {
+        self.at_arg(index, |c| c.check(&args[index]))
+    }
+
+    fn expect_arg<T>(&mut self, args: &[Expression<T>], index: usize, expected: Type) -> Checked {
+        let found = self.check_arg(args, index)?;
+        if expected.accepts(found) {
+            Ok(found)
+        } else {
+            Err(self.at_arg(index, |c| c.mismatch(expected, found)))
+        }
+    }
+
+    fn number_literal<T>(
+        &mut self,
+        args: &[Expression<T>],
+        index: usize,
+    ) -> std::result::Result<f64, ValidationError> {
+        match &args[index] {
+            Expression::Number(n) => Ok(*n),
+            _ => Err(self.at_arg(index, |c| c.invalid("expected a number literal"))),
+        }
+    }
+
+    fn arity(&self, op: &str, expected: Arity, found: usize) -> ValidationError {
+        ValidationError::WrongArity {
+            path: self.path(),
+            operator: op.into(),
+            expected,
+            found,
+        }
+    }
+
+    /// Unify the output types of branches, e.g. of `case` or `match`.
+    fn unify<T>(
+        &mut self,
+        args: &[Expression<T>],
+        indices: impl IntoIterator<Item = usize>,
+    ) -> Checked {
+        use Type::*;
+
+        let mut output = Value;
+        for index in indices {
+            let found = self.check_arg(args, index)?;
+            output = match (output, found) {
+                (Value, found) => found,
+                // A string may be a color, formatted text or an image name in any branch.
+                (String, Color | Formatted | ResolvedImage) => found,
+                (output, _) if output.accepts(found) => output,
+                (output, found) => return Err(self.at_arg(index, |c| c.mismatch(output, found))),
+            };
+        }
+        Ok(output)
+    }
+
+    fn check_call<T>(&mut self, op: &str, args: &[Expression<T>]) -> Checked {
+        match op {
+            "literal" => self.check_literal(args),
+            "array" => self.check_array(args),
+            "format" => self.check_format(args),
+            "let" => self.check_let(args),
+            "var" => self.check_var(args),
+            "case" => self.check_case(args),
+            "match" => self.check_match(args),
+            "coalesce" => {
+                if args.is_empty() {
+                    return Err(self.arity(op, Arity::AtLeast(1), 0));
+                }
+                self.unify(args, 0..args.len())
+            }
+            "step" => self.check_step(args),
+            "interpolate" | "interpolate-hcl" | "interpolate-lab" => {
+                self.check_interpolate(op, args)
+            }
+            "==" | "!=" | "<" | "<=" | ">" | ">=" => self.check_comparison(op, args),
+            _ => self.check_signature(op, args),
+        }
+    }
+
+    fn check_signature<T>(&mut self, op: &str, args: &[Expression<T>]) -> Checked {
+        let Some((params, output)) = signature(op) else {
+            return Err(ValidationError::UnknownOperator {
+                path: self.path(),
+                name: op.into(),
+            });
+        };
+
+        match params {
+            Params::Fixed(overloads) => {
+                let Some(params) = overloads.iter().find(|p| p.len() == args.len()) else {
+                    let expected = match overloads {
+                        [params] => Arity::Exactly(params.len()),
+                        _ => Arity::OneOf(overloads.iter().map(|p| p.len()).collect()),
+                    };
+                    return Err(self.arity(op, expected, args.len()));
+                };
+                for (index, param) in params.iter().enumerate() {
+                    self.expect_arg(args, index, *param)?;
+                }
+            }
+            Params::Variadic { param, min } => {
+                if args.len() < min {
+                    return Err(self.arity(op, Arity::AtLeast(min), args.len()));
+                }
+                for index in 0..args.len() {
+                    self.expect_arg(args, index, param)?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn check_literal<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.len() != 1 {
+            return Err(self.arity("literal", Arity::Exactly(1), args.len()));
+        }
+        Ok(match &args[0] {
+            Expression::Enum(_) | Expression::String(_) => Type::String,
+            Expression::Number(_) => Type::Number,
+            Expression::Bool(_) => Type::Boolean,
+            Expression::List(_) => Type::Array,
+            Expression::Object(_) => Type::Object,
+        })
+    }
+
+    fn check_array<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        // ["array", value], ["array", type, value] or ["array", type, length, value]
+        match args.len() {
+            1 => {}
+            2 | 3 => {
+                if !matches!(&args[0], Expression::String(ty) if matches!(ty.as_str(), "string" | "number" | "boolean"))
+                {
+                    return Err(self.at_arg(0, |c| {
+                        c.invalid("array item type must be \"string\", \"number\" or \"boolean\"")
+                    }));
+                }
+                if args.len() == 3 {
+                    self.number_literal(args, 1)?;
+                }
+            }
+            n => return Err(self.arity("array", Arity::OneOf(vec![1, 2, 3]), n)),
+        }
+        self.check_arg(args, args.len() - 1)?;
+        Ok(Type::Array)
+    }
+
+    fn check_format<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.is_empty() {
+            return Err(self.arity("format", Arity::AtLeast(1), 0));
+        }
+        for (index, arg) in args.iter().enumerate() {
+            match arg {
+                // Section options such as {"font-scale": 1.2}
+                Expression::Object(options) if index > 0 => {
+                    self.at_arg(index, |c| c.check_format_options(options))?;
+                }
+                _ => {
+                    let found = self.check_arg(args, index)?;
+                    if !matches!(
+                        found,
+                        Type::String | Type::Formatted | Type::ResolvedImage | Type::Value
+                    ) {
+                        return Err(self.at_arg(index, |c| c.mismatch(Type::Formatted, found)));
+                    }
+                }
+            }
+        }
+        Ok(Type::Formatted)
+    }
+
+    fn check_format_options<T>(
+        &mut self,
+        options: &BTreeMap<String, Expression<T>>,
+    ) -> std::result::Result<(), ValidationError> {
+        for (key, value) in options {
+            let expected = match key.as_str() {
+                "font-scale" => Type::Number,
+                "text-font" => Type::Array,
+                "text-color" => Type::Color,
+                "vertical-align" => Type::String,
+                _ => return Err(self.invalid(format!("unknown format option \"{key}\""))),
+            };
+            let found = self.check(value)?;
+            if !expected.accepts(found) {
+                return Err(self.mismatch(expected, found));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_let<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.len() < 3 || args.len().is_multiple_of(2) {
+            return Err(self.arity("let", Arity::AtLeast(3), args.len()));
+        }
+        let mut scope = HashMap::new();
+        for index in (0..args.len() - 1).step_by(2) {
+            let Expression::String(name) = &args[index] else {
+                return Err(self.at_arg(index, |c| {
+                    c.invalid("variable name must be a string literal")
+                }));
+            };
+            let ty = self.check_arg(args, index + 1)?;
+            scope.insert(name.clone(), ty);
+        }
+        self.scopes.push(scope);
+        let res = self.check_arg(args, args.len() - 1);
+        self.scopes.pop();
+        res
+    }
+
+    fn check_var<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.len() != 1 {
+            return Err(self.arity("var", Arity::Exactly(1), args.len()));
+        }
+        let Expression::String(name) = &args[0] else {
+            return Err(self.invalid("variable name must be a string literal"));
+        };
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+            .ok_or_else(|| self.invalid(format!("unknown variable \"{name}\"")))
+    }
+
+    fn check_case<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.len() < 3 || args.len().is_multiple_of(2) {
+            return Err(self.arity("case", Arity::AtLeast(3), args.len()));
+        }
+        for index in (0..args.len() - 1).step_by(2) {
+            self.expect_arg(args, index, Type::Boolean)?;
+        }
+        let outputs = (1..args.len()).step_by(2).chain([args.len() - 1]);
+        self.unify(args, outputs)
+    }
+
+    fn check_match<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.len() < 4 || !args.len().is_multiple_of(2) {
+            return Err(self.arity("match", Arity::AtLeast(4), args.len()));
+        }
+        self.check_arg(args, 0)?;
+        for index in (1..args.len() - 1).step_by(2) {
+            let valid = match &args[index] {
+                Expression::Number(_) | Expression::String(_) => true,
+                Expression::List(labels) => labels
+                    .iter()
+                    .all(|l| matches!(l, Expression::Number(_) | Expression::String(_))),
+                _ => false,
+            };
+            if !valid {
+                return Err(self.at_arg(index, |c| {
+                    c.invalid("match labels must be number or string literals")
+                }));
+            }
+        }
+        let outputs = (2..args.len()).step_by(2).chain([args.len() - 1]);
+        self.unify(args, outputs)
+    }
+
+    fn check_stops<T>(
+        &mut self,
+        args: &[Expression<T>],
+        first: usize,
+    ) -> std::result::Result<(), ValidationError> {
+        let mut previous = f64::NEG_INFINITY;
+        for index in (first..args.len()).step_by(2) {
+            let stop = self.number_literal(args, index)?;
+            if stop <= previous {
+                return Err(self.at_arg(index, |c| {
+                    c.invalid("stop inputs must be in strictly ascending order")
+                }));
+            }
+            previous = stop;
+        }
+        Ok(())
+    }
+
+    fn check_step<T>(&mut self, args: &[Expression<T>]) -> Checked {
+        if args.len() < 2 || !args.len().is_multiple_of(2) {
+            return Err(self.arity("step", Arity::AtLeast(2), args.len()));
+        }
+        self.expect_arg(args, 0, Type::Number)?;
+        self.check_stops(args, 2)?;
+        let outputs = std::iter::once(1).chain((3..args.len()).step_by(2));
+        self.unify(args, outputs)
+    }
+
+    fn check_interpolate<T>(&mut self, op: &str, args: &[Expression<T>]) -> Checked {
+        if args.len() < 4 || !args.len().is_multiple_of(2) {
+            return Err(self.arity(op, Arity::AtLeast(4), args.len()));
+        }
+
+        self.at_arg(0, |c| match &args[0] {
+            Expression::List(list) => match list.first() {
+                Some(Expression::String(ty)) => match (ty.as_str(), &list[1..]) {
+                    ("linear", []) | ("exponential", [Expression::Number(_)]) => Ok(()),
+                    ("cubic-bezier", [x1, y1, x2, y2])
+                        if [x1, y1, x2, y2]
+                            .iter()
+                            .all(|n| matches!(n, Expression::Number(_))) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(c.invalid(format!("invalid interpolation type \"{ty}\""))),
+                },
+                _ => Err(c.invalid("expected an interpolation type")),
+            },
+            _ => Err(c.invalid("expected an interpolation type")),
+        })?;
+
+        self.expect_arg(args, 1, Type::Number)?;
+        self.check_stops(args, 2)?;
+        let output = match self.unify(args, (3..args.len()).step_by(2))? {
+            // String outputs can only be interpolated as color literals.
+            Type::String => Type::Color,
+            output => output,
+        };
+
+        let valid = match op {
+            "interpolate" => matches!(
+                output,
+                Type::Number | Type::Color | Type::Array | Type::Value
+            ),
+            _ => matches!(output, Type::Color | Type::Value),
+        };
+        if !valid {
+            let expected = if op == "interpolate" {
+                Type::Number
+            } else {
+                Type::Color
+            };
+            return Err(self.at_arg(3, |c| c.mismatch(expected, output)));
+        }
+
+        Ok(output)
+    }
+
+    fn check_comparison<T>(&mut self, op: &str, args: &[Expression<T>]) -> Checked {
+        if !matches!(args.len(), 2 | 3) {
+            return Err(self.arity(op, Arity::OneOf(vec![2, 3]), args.len()));
+        }
+        let lhs = self.check_arg(args, 0)?;
+        if matches!(op, "<" | "<=" | ">" | ">=")
+            && !matches!(lhs, Type::Number | Type::String | Type::Value)
+        {
+            return Err(self.at_arg(0, |c| c.mismatch(Type::Number, lhs)));
+        }
+        self.expect_arg(args, 1, lhs)?;
+        if args.len() == 3 {
+            self.expect_arg(args, 2, Type::Collator)?;
+        }
+        Ok(Type::Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use serde_json::json;
+
+    fn expr(value: serde_json::Value) -> Expression<()> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn error(value: serde_json::Value) -> ValidationError {
+        match validate(&expr(value)) {
+            Err(Error::InvalidExpression(e)) => e,
+            res => panic!("expected a validation error, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_operator() {
+        assert_eq!(
+            error(json!(["gett", "pop"])),
+            ValidationError::UnknownOperator {
+                path: ExpressionPath(vec![]),
+                name: "gett".into(),
+            }
+        );
+        assert!(matches!(
+            error(json!(["zom"])),
+            ValidationError::UnknownOperator { .. }
+        ));
+        assert!(matches!(
+            error(json!(["+", ["zom"], 1])),
+            ValidationError::UnknownOperator { path, .. } if path == ExpressionPath(vec![1])
+        ));
+    }
+
+    #[test]
+    fn constant_array_only_where_an_array_is_expected() {
+        let font = expr(json!(["Open Sans Regular", "Arial Unicode MS Regular"]));
+        assert!(validate_as(&font, Type::Array).is_ok());
+        assert!(validate(&font).is_err());
+        assert!(validate_as(&font, Type::String).is_err());
+    }
+
+    #[test]
+    fn wrong_arity() {
+        assert_eq!(
+            error(json!(["get"])),
+            ValidationError::WrongArity {
+                path: ExpressionPath(vec![]),
+                operator: "get".into(),
+                expected: Arity::OneOf(vec![1, 2]),
+                found: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn type_mismatch() {
+        assert_eq!(
+            error(json!(["+", "a", 1])),
+            ValidationError::TypeMismatch {
+                path: ExpressionPath(vec![1]),
+                expected: Type::Number,
+                found: Type::String,
+            }
+        );
+    }
+
+    #[test]
+    fn nested_case_and_match_fallback() {
+        let valid = json!([
+            "case",
+            ["has", "name"],
+            ["match", ["get", "class"], "park", 1, 0],
+            0.5
+        ]);
+        assert_eq!(validate(&expr(valid)).unwrap(), Type::Number);
+
+        let bad_fallback = json!([
+            "case",
+            ["has", "name"],
+            ["match", ["get", "class"], "park", 1, ["zom"]],
+            0.5
+        ]);
+        assert!(matches!(
+            error(bad_fallback),
+            ValidationError::UnknownOperator { path, .. } if path == ExpressionPath(vec![2, 4])
+        ));
+    }
+
+    #[test]
+    fn interpolate() {
+        let valid = json!(["interpolate", ["linear"], ["zoom"], 5, 1, 10, 4]);
+        assert_eq!(validate(&expr(valid)).unwrap(), Type::Number);
+        let valid = json!([
+            "interpolate",
+            ["exponential", 2],
+            ["zoom"],
+            5,
+            "#fff",
+            10,
+            "#000"
+        ]);
+        assert!(validate_as(&expr(valid), Type::Color).is_ok());
+    }
+
+    #[test]
+    fn string_widens_to_color_in_either_order() {
+        let rgb = json!(["rgb", 0, 0, 0]);
+        for (a, b) in [(json!("#fff"), rgb.clone()), (rgb.clone(), json!("#fff"))] {
+            for value in [
+                json!(["case", ["has", "a"], a, b]),
+                json!(["match", ["get", "class"], "park", a, b]),
+                json!(["step", ["zoom"], a, 10, b]),
+                json!(["interpolate", ["linear"], ["zoom"], 5, a, 10, b]),
+                json!(["coalesce", a, b]),
+            ] {
+                assert_eq!(
+                    validate(&expr(value.clone())).unwrap(),
+                    Type::Color,
+                    "{value}"
+                );
+            }
+        }
+
+        let built = crate::expr::case(
+            [(crate::expr::has("a"), crate::expr::color("#fff"))],
+            crate::expr::rgb(0.0, 0.0, 0.0),
+        );
+        let built: Expression<()> = serde_json::to_value(&built)
+            .and_then(serde_json::from_value)
+            .unwrap();
+        assert_eq!(validate(&built).unwrap(), Type::Color);
+    }
+
+    #[test]
+    fn string_widens_to_formatted_and_image() {
+        let formatted = json!(["format", "a", {}]);
+        let image = json!(["image", "park"]);
+        for (a, b, expected) in [
+            (json!("b"), formatted.clone(), Type::Formatted),
+            (formatted, json!("b"), Type::Formatted),
+            (json!("b"), image.clone(), Type::ResolvedImage),
+            (image, json!("b"), Type::ResolvedImage),
+        ] {
+            let value = json!(["case", ["has", "a"], a, b]);
+            assert_eq!(validate(&expr(value)).unwrap(), expected);
+        }
+
+        assert_eq!(
+            error(json!(["case", ["has", "a"], 1, ["rgb", 0, 0, 0]])),
+            ValidationError::TypeMismatch {
+                path: ExpressionPath(vec![3]),
+                expected: Type::Number,
+                found: Type::Color,
+            }
+        );
+    }
+}
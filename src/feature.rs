@@ -0,0 +1,127 @@
+use crate::{layer::GetLayer, Error, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+
+/// Identifies a feature whose state is read or written with the feature state API.
+///
+/// https://docs.mapbox.com/mapbox-gl-js/api/map/#map#setfeaturestate
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureIdentifier {
+    pub source: String,
+    /// Required for vector tile sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_layer: Option<String>,
+    pub id: geojson::feature::Id,
+}
+
+impl FeatureIdentifier {
+    pub fn new(source: impl Into<String>, id: geojson::feature::Id) -> FeatureIdentifier {
+        FeatureIdentifier {
+            source: source.into(),
+            source_layer: None,
+            id,
+        }
+    }
+}
+
+/// Feature found under the cursor or by a query, along with where it is rendered from.
+#[derive(Debug, Clone)]
+pub struct RenderedFeature {
+    pub feature: geojson::Feature,
+    pub layer: Option<GetLayer>,
+    pub source: Option<String>,
+    pub source_layer: Option<String>,
+    pub state: geojson::JsonObject,
+}
+
+impl RenderedFeature {
+    /// Deserializes the properties of the feature into `T`.
+    pub fn properties<T: DeserializeOwned>(&self) -> Result<T> {
+        let properties = self.feature.properties.clone().unwrap_or_default();
+        serde_json::from_value(serde_json::Value::Object(properties))
+            .map_err(|e| Error::BadGeoJson(e.to_string()))
+    }
+}
+
+// It seems GeoJSON returned from mapbox-gl-js contains byte array, which causes
+// deserialize error with geojson crate. geojson crate internally deserialize all
+// the properties into Map.
+//
+// As a workaround, use an intermediate "Feature" struct to deserialize from
+// JsValue using serde, then convert it to geojson::Feature.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Id {
+    String(String),
+    Number(serde_json::Number),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Feature {
+    bbox: Option<geojson::Bbox>,
+    geometry: Option<geojson::Geometry>,
+    id: Option<Id>,
+    properties: Option<geojson::JsonObject>,
+    foreign_members: Option<geojson::JsonObject>,
+    layer: Option<GetLayer>,
+    source: Option<String>,
+    source_layer: Option<String>,
+    state: Option<geojson::JsonObject>,
+}
+
+impl From<Feature> for RenderedFeature {
+    fn from(f: Feature) -> Self {
+        RenderedFeature {
+            feature: geojson::Feature {
+                bbox: f.bbox,
+                geometry: f.geometry,
+                id: f.id.map(|id| match id {
+                    Id::String(s) => geojson::feature::Id::String(s),
+                    Id::Number(n) => geojson::feature::Id::Number(n),
+                }),
+                properties: f.properties,
+                foreign_members: f.foreign_members,
+            },
+            layer: f.layer,
+            source: f.source,
+            source_layer: f.source_layer,
+            state: f.state.unwrap_or_default(),
+        }
+    }
+}
+
+/// Converts an array of mapbox-gl-js features. `undefined` is treated as no features.
+pub(crate) fn rendered_features_from_js(value: JsValue) -> Result<Vec<RenderedFeature>> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(vec![]);
+    }
+    let features: Vec<Feature> = serde_wasm_bindgen::from_value(value)?;
+    Ok(features.into_iter().map(RenderedFeature::from).collect())
+}
+
+/// Like [`rendered_features_from_js`] but skips the features that fail to convert, so a
+/// single bad feature doesn't drop a whole mouse or touch event.
+pub(crate) fn rendered_features_from_js_lossy(value: JsValue) -> Vec<RenderedFeature> {
+    if !js_sys::Array::is_array(&value) {
+        return vec![];
+    }
+    js_sys::Array::from(&value)
+        .iter()
+        .filter_map(|f| match serde_wasm_bindgen::from_value::<Feature>(f) {
+            Ok(f) => Some(f.into()),
+            Err(e) => {
+                log::warn!("Skipping a feature that failed to deserialize: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn features_from_js(value: JsValue) -> Result<Vec<geojson::Feature>> {
+    Ok(rendered_features_from_js(value)?
+        .into_iter()
+        .map(|f| f.feature)
+        .collect())
+}
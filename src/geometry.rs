@@ -1,76 +1,135 @@
-use crate::LngLatBounds;
+use crate::{LngLat, LngLatBounds, Map, Result};
 use serde::{Deserialize, Serialize};
 
+/// Position in pixels relative to the top left corner of the map container.
+///
+/// https://docs.mapbox.com/mapbox-gl-js/api/geography/#point
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl ScreenPoint {
+    pub fn new(x: f64, y: f64) -> ScreenPoint {
+        ScreenPoint { x, y }
+    }
+
+    /// mapbox-gl-js `PointLike` array.
+    pub(crate) fn to_array(self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl From<(f64, f64)> for ScreenPoint {
+    fn from((x, y): (f64, f64)) -> Self {
+        ScreenPoint { x, y }
+    }
+}
+
+impl From<[f64; 2]> for ScreenPoint {
+    fn from([x, y]: [f64; 2]) -> Self {
+        ScreenPoint { x, y }
+    }
+}
+
+impl From<crate::event::Point> for ScreenPoint {
+    fn from(p: crate::event::Point) -> Self {
+        ScreenPoint { x: p.x, y: p.y }
+    }
+}
+
 pub trait IntoQueryGeometry {
-    fn into_query_geometry(self) -> QueryGeometry;
+    fn into_query_geometry(self, map: &Map) -> Result<QueryGeometry>;
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Area in pixels to query rendered features in.
+///
+/// https://docs.mapbox.com/mapbox-gl-js/api/map/#map#queryrenderedfeatures
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum QueryGeometry {
-    Point {
-        lng: f64,
-        lat: f64,
-    },
-    BBox {
-        west: f64,
-        south: f64,
-        east: f64,
-        north: f64,
-    },
-}
-
-impl QueryGeometry {
-    pub fn into_vec(self) -> Vec<f64> {
+    Point(ScreenPoint),
+    /// Box between two opposite corners.
+    Box(ScreenPoint, ScreenPoint),
+}
+
+impl Serialize for QueryGeometry {
+    fn serialize<S>(&self, ser: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
         match self {
-            Self::Point { lng, lat } => vec![lng, lat],
-            Self::BBox {
-                west,
-                south,
-                east,
-                north,
-            } => vec![west, south, east, north],
+            Self::Point(p) => p.to_array().serialize(ser),
+            Self::Box(a, b) => [a.to_array(), b.to_array()].serialize(ser),
         }
     }
 }
 
+impl IntoQueryGeometry for QueryGeometry {
+    fn into_query_geometry(self, _map: &Map) -> Result<QueryGeometry> {
+        Ok(self)
+    }
+}
+
+impl IntoQueryGeometry for ScreenPoint {
+    fn into_query_geometry(self, _map: &Map) -> Result<QueryGeometry> {
+        Ok(QueryGeometry::Point(self))
+    }
+}
+
+impl IntoQueryGeometry for [ScreenPoint; 2] {
+    fn into_query_geometry(self, _map: &Map) -> Result<QueryGeometry> {
+        Ok(QueryGeometry::Box(self[0], self[1]))
+    }
+}
+
 impl IntoQueryGeometry for [f64; 2] {
-    fn into_query_geometry(self) -> QueryGeometry {
-        QueryGeometry::Point {
-            lng: self[0],
-            lat: self[1],
-        }
+    fn into_query_geometry(self, _map: &Map) -> Result<QueryGeometry> {
+        Ok(QueryGeometry::Point(self.into()))
     }
 }
 
+/// Box between two opposite corners in pixels as `[x0, y0, x1, y1]`.
 impl IntoQueryGeometry for [f64; 4] {
-    fn into_query_geometry(self) -> QueryGeometry {
-        QueryGeometry::BBox {
-            west: self[0],
-            south: self[1],
-            east: self[2],
-            north: self[3],
-        }
+    fn into_query_geometry(self, _map: &Map) -> Result<QueryGeometry> {
+        let [x0, y0, x1, y1] = self;
+        Ok(QueryGeometry::Box(
+            ScreenPoint::new(x0, y0),
+            ScreenPoint::new(x1, y1),
+        ))
     }
 }
 
 impl IntoQueryGeometry for crate::event::Point {
-    fn into_query_geometry(self) -> QueryGeometry {
-        QueryGeometry::Point {
-            lng: self.x,
-            lat: self.y,
-        }
+    fn into_query_geometry(self, _map: &Map) -> Result<QueryGeometry> {
+        Ok(QueryGeometry::Point(self.into()))
     }
 }
 
+impl IntoQueryGeometry for LngLat {
+    fn into_query_geometry(self, map: &Map) -> Result<QueryGeometry> {
+        Ok(QueryGeometry::Point(map.project(&self)?))
+    }
+}
+
+/// Projects the corners of the bounds and queries the box around them.
 impl IntoQueryGeometry for LngLatBounds {
-    fn into_query_geometry(self) -> QueryGeometry {
-        let sw = self.get_south_west();
-        let ne = self.get_north_east();
-        QueryGeometry::BBox {
-            west: sw.lng(),
-            south: sw.lat(),
-            east: ne.lng(),
-            north: ne.lat(),
-        }
+    fn into_query_geometry(self, map: &Map) -> Result<QueryGeometry> {
+        let (sw, ne) = (self.get_south_west(), self.get_north_east());
+        let corners = [
+            map.project(&sw)?,
+            map.project(&ne)?,
+            map.project(&LngLat::new(sw.lng(), ne.lat()))?,
+            map.project(&LngLat::new(ne.lng(), sw.lat()))?,
+        ];
+        let (min, max) = corners
+            .iter()
+            .fold((corners[0], corners[0]), |(min, max), p| {
+                (
+                    ScreenPoint::new(min.x.min(p.x), min.y.min(p.y)),
+                    ScreenPoint::new(max.x.max(p.x), max.y.max(p.y)),
+                )
+            });
+        Ok(QueryGeometry::Box(min, max))
     }
 }
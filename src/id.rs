@@ -9,6 +9,10 @@ pub struct MapListenerId(pub uuid::Uuid);
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct MarkerId(pub uuid::Uuid);
 
+/// ID for a popup.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PopupId(pub uuid::Uuid);
+
 /// ID for a callback.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct CallbackId(pub uuid::Uuid);
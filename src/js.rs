@@ -119,15 +119,73 @@ extern "C" {
         callback: &Closure<dyn Fn(JsValue)>,
     );
 
+    #[wasm_bindgen(method)]
+    pub fn once(this: &Map, r#type: String, callback: &Closure<dyn Fn(JsValue)>);
+
+    #[wasm_bindgen(method, js_name=once)]
+    pub fn once_layer(
+        this: &Map,
+        r#type: String,
+        layer_id: String,
+        callback: &Closure<dyn Fn(JsValue)>,
+    );
+
+    #[wasm_bindgen(method)]
+    pub fn off(this: &Map, r#type: String, callback: &Closure<dyn Fn(JsValue)>);
+
+    #[wasm_bindgen(method, js_name=off)]
+    pub fn off_layer(
+        this: &Map,
+        r#type: String,
+        layer_id: String,
+        callback: &Closure<dyn Fn(JsValue)>,
+    );
+
     #[wasm_bindgen(method)]
     pub fn getContainer(this: &Map) -> web_sys::HtmlElement;
 
     #[wasm_bindgen(method)]
     pub fn getMinZoom(this: &Map) -> f64;
 
+    #[wasm_bindgen(method)]
+    pub fn setMinZoom(this: &Map, minZoom: Option<f64>);
+
+    #[wasm_bindgen(method)]
+    pub fn getMaxZoom(this: &Map) -> f64;
+
+    #[wasm_bindgen(method)]
+    pub fn setMaxZoom(this: &Map, maxZoom: Option<f64>);
+
+    #[wasm_bindgen(method)]
+    pub fn getMinPitch(this: &Map) -> f64;
+
+    #[wasm_bindgen(method)]
+    pub fn setMinPitch(this: &Map, minPitch: Option<f64>);
+
+    #[wasm_bindgen(method)]
+    pub fn getMaxPitch(this: &Map) -> f64;
+
+    #[wasm_bindgen(method)]
+    pub fn setMaxPitch(this: &Map, maxPitch: Option<f64>);
+
+    #[wasm_bindgen(method)]
+    pub fn getMaxBounds(this: &Map) -> Option<LngLatBounds>;
+
+    #[wasm_bindgen(method)]
+    pub fn setMaxBounds(this: &Map, bounds: Option<&LngLatBounds>);
+
+    #[wasm_bindgen(method)]
+    pub fn getRenderWorldCopies(this: &Map) -> bool;
+
+    #[wasm_bindgen(method)]
+    pub fn setRenderWorldCopies(this: &Map, renderWorldCopies: bool);
+
     #[wasm_bindgen(method)]
     pub fn isMoving(this: &Map) -> bool;
 
+    #[wasm_bindgen(method)]
+    pub fn isEasing(this: &Map) -> bool;
+
     #[wasm_bindgen(method)]
     pub fn isZooming(this: &Map) -> bool;
 
@@ -187,6 +245,19 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn queryRenderedFeatures(map: &Map, geometry: JsValue, options: JsValue) -> JsValue;
 
+    #[wasm_bindgen(method)]
+    pub fn querySourceFeatures(map: &Map, source_id: String, options: JsValue) -> JsValue;
+
+    // Feature state
+    #[wasm_bindgen(method)]
+    pub fn setFeatureState(this: &Map, feature: JsValue, state: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn getFeatureState(this: &Map, feature: JsValue) -> JsValue;
+
+    #[wasm_bindgen(method)]
+    pub fn removeFeatureState(this: &Map, target: JsValue, key: Option<String>);
+
     // Sources
     #[wasm_bindgen(method)]
     pub fn addSource(this: &Map, id: String, source: JsValue);
@@ -293,6 +364,73 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn getCenter(this: &Map) -> LngLat;
 
+    #[wasm_bindgen(method)]
+    pub fn setCenter(this: &Map, center: &LngLat, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn setZoom(this: &Map, zoom: f64, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn getBearing(this: &Map) -> f64;
+
+    #[wasm_bindgen(method)]
+    pub fn setBearing(this: &Map, bearing: f64, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn getPitch(this: &Map) -> f64;
+
+    #[wasm_bindgen(method)]
+    pub fn setPitch(this: &Map, pitch: f64, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn zoomTo(this: &Map, zoom: f64, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn zoomIn(this: &Map, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn zoomOut(this: &Map, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn rotateTo(this: &Map, bearing: f64, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn resetNorth(this: &Map, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn resetNorthPitch(this: &Map, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn snapToNorth(this: &Map, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn panBy(this: &Map, offset: JsValue, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn stop(this: &Map);
+
+    #[wasm_bindgen(method)]
+    pub fn project(this: &Map, lngLat: &LngLat) -> JsValue;
+
+    #[wasm_bindgen(method)]
+    pub fn unproject(this: &Map, point: JsValue) -> LngLat;
+
+    #[wasm_bindgen(method)]
+    pub fn fitBounds(this: &Map, bounds: &LngLatBounds, options: JsValue, eventData: JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn fitScreenCoordinates(
+        this: &Map,
+        p0: JsValue,
+        p1: JsValue,
+        bearing: f64,
+        options: JsValue,
+        eventData: JsValue,
+    );
+
+    #[wasm_bindgen(method)]
+    pub fn cameraForBounds(this: &Map, bounds: &LngLatBounds, options: JsValue) -> JsValue;
+
     // --
 
     pub type BoxZoomHandler;
@@ -340,6 +478,42 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn getLngLat(this: &Marker) -> LngLat;
 
+    #[wasm_bindgen(method, js_name=getElement)]
+    pub fn Marker_getElement(this: &Marker) -> web_sys::HtmlElement;
+
+    #[wasm_bindgen(method, js_name=setOffset)]
+    pub fn Marker_setOffset(this: &Marker, offset: JsValue);
+
+    #[wasm_bindgen(method, js_name=setRotation)]
+    pub fn Marker_setRotation(this: &Marker, rotation: f64);
+
+    #[wasm_bindgen(method, js_name=getRotation)]
+    pub fn Marker_getRotation(this: &Marker) -> f64;
+
+    #[wasm_bindgen(method, js_name=setDraggable)]
+    pub fn Marker_setDraggable(this: &Marker, draggable: bool);
+
+    #[wasm_bindgen(method, js_name=isDraggable)]
+    pub fn Marker_isDraggable(this: &Marker) -> bool;
+
+    #[wasm_bindgen(method, js_name=setPitchAlignment)]
+    pub fn Marker_setPitchAlignment(this: &Marker, alignment: &str);
+
+    #[wasm_bindgen(method, js_name=setRotationAlignment)]
+    pub fn Marker_setRotationAlignment(this: &Marker, alignment: &str);
+
+    #[wasm_bindgen(method, js_name=setPopup)]
+    pub fn Marker_setPopup(this: &Marker, popup: Option<&Popup>);
+
+    #[wasm_bindgen(method, js_name=togglePopup)]
+    pub fn Marker_togglePopup(this: &Marker);
+
+    #[wasm_bindgen(method, js_name=addClassName)]
+    pub fn Marker_addClassName(this: &Marker, class_name: &str);
+
+    #[wasm_bindgen(method, js_name=removeClassName)]
+    pub fn Marker_removeClassName(this: &Marker, class_name: &str);
+
     // --
 
     pub type Popup;
@@ -356,10 +530,103 @@ extern "C" {
     #[wasm_bindgen(method, js_name=addTo)]
     pub fn Popup_addTo(this: &Popup, map: &Map);
 
+    #[wasm_bindgen(method, js_name=getLngLat)]
+    pub fn Popup_getLngLat(this: &Popup) -> LngLat;
+
+    #[wasm_bindgen(method, js_name=setText)]
+    pub fn Popup_setText(this: &Popup, text: &str);
+
+    #[wasm_bindgen(method, js_name=setDOMContent)]
+    pub fn Popup_setDOMContent(this: &Popup, node: &web_sys::Node);
+
+    #[wasm_bindgen(method, js_name=setMaxWidth)]
+    pub fn Popup_setMaxWidth(this: &Popup, max_width: &str);
+
+    #[wasm_bindgen(method, js_name=trackPointer)]
+    pub fn Popup_trackPointer(this: &Popup);
+
+    #[wasm_bindgen(method, js_name=isOpen)]
+    pub fn Popup_isOpen(this: &Popup) -> bool;
+
+    #[wasm_bindgen(method, js_name=remove)]
+    pub fn Popup_remove(this: &Popup);
+
+    #[wasm_bindgen(method, js_name=on)]
+    pub fn Popup_on(this: &Popup, r#type: String, callback: &Closure<dyn Fn(JsValue)>);
+
     // --
 
     pub type GeoJSONSource;
 
     #[wasm_bindgen(method, js_name=setData)]
     pub fn GeoJSONSource_setData(this: &GeoJSONSource, data: &JsValue);
+
+    #[wasm_bindgen(method, js_name=updateData)]
+    pub fn GeoJSONSource_updateData(this: &GeoJSONSource, data: &JsValue);
+
+    #[wasm_bindgen(method, js_name=getClusterExpansionZoom)]
+    pub fn GeoJSONSource_getClusterExpansionZoom(
+        this: &GeoJSONSource,
+        cluster_id: f64,
+        callback: &js_sys::Function,
+    );
+
+    #[wasm_bindgen(method, js_name=getClusterChildren)]
+    pub fn GeoJSONSource_getClusterChildren(
+        this: &GeoJSONSource,
+        cluster_id: f64,
+        callback: &js_sys::Function,
+    );
+
+    #[wasm_bindgen(method, js_name=getClusterLeaves)]
+    pub fn GeoJSONSource_getClusterLeaves(
+        this: &GeoJSONSource,
+        cluster_id: f64,
+        limit: f64,
+        offset: f64,
+        callback: &js_sys::Function,
+    );
+
+    // --
+
+    pub type VectorTileSource;
+
+    #[wasm_bindgen(method, js_name=setTiles)]
+    pub fn VectorTileSource_setTiles(this: &VectorTileSource, tiles: JsValue);
+
+    #[wasm_bindgen(method, js_name=setUrl)]
+    pub fn VectorTileSource_setUrl(this: &VectorTileSource, url: String);
+
+    // --
+
+    pub type RasterTileSource;
+
+    #[wasm_bindgen(method, js_name=setTiles)]
+    pub fn RasterTileSource_setTiles(this: &RasterTileSource, tiles: JsValue);
+
+    #[wasm_bindgen(method, js_name=setUrl)]
+    pub fn RasterTileSource_setUrl(this: &RasterTileSource, url: String);
+
+    // --
+
+    pub type ImageSource;
+
+    #[wasm_bindgen(method, js_name=updateImage)]
+    pub fn ImageSource_updateImage(this: &ImageSource, options: JsValue);
+
+    #[wasm_bindgen(method, js_name=setCoordinates)]
+    pub fn ImageSource_setCoordinates(this: &ImageSource, coordinates: JsValue);
+
+    // --
+
+    pub type VideoSource;
+
+    #[wasm_bindgen(method, js_name=play)]
+    pub fn VideoSource_play(this: &VideoSource);
+
+    #[wasm_bindgen(method, js_name=pause)]
+    pub fn VideoSource_pause(this: &VideoSource);
+
+    #[wasm_bindgen(method, js_name=setCoordinates)]
+    pub fn VideoSource_setCoordinates(this: &VideoSource, coordinates: JsValue);
 }
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::{closure::Closure, JsValue};
@@ -11,6 +11,7 @@ pub enum Expression<T> {
     Number(f64),
     Bool(bool),
     List(Vec<Expression<T>>),
+    Object(BTreeMap<String, Expression<T>>),
 }
 
 pub trait EnumMarker {}
@@ -56,8 +57,9 @@ pub enum Visibility {
     Visible,
     None,
 }
+impl EnumMarker for Visibility {}
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetLayer {
     pub id: String,
     #[serde(rename = "type")]
@@ -70,8 +72,8 @@ pub struct GetLayer {
     pub visibility: Option<Visibility>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
 #[allow(clippy::large_enum_variant)]
 pub enum Layer {
     Custom(CustomLayer),
@@ -87,12 +89,75 @@ pub enum Layer {
     Hillshade(HillshadeLayer),
     Sky(SkyLayer),
     Model(ModelLayer),
+    Slot(SlotLayer),
+    Clip(ClipLayer),
+    /// Layer of a type not modeled by this crate, e.g. `building`, kept as it is.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for Layer {
+    fn deserialize<D>(de: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        use serde_json::Value;
+
+        let mut layer = serde_json::Map::deserialize(de)?;
+        let r#type = match layer.remove("type") {
+            Some(Value::String(r#type)) => r#type,
+            Some(other) => return Err(D::Error::custom(format!("invalid layer type {other}"))),
+            None => return Err(D::Error::missing_field("type")),
+        };
+
+        macro_rules! known {
+            ($($name:literal => $variant:ident),* $(,)?) => {
+                match r#type.as_str() {
+                    $($name => serde_json::from_value(Value::Object(layer)).map(Layer::$variant),)*
+                    _ => {
+                        layer.insert("type".into(), Value::String(r#type));
+                        Ok(Layer::Other(Value::Object(layer)))
+                    }
+                }
+            };
+        }
+
+        known!(
+            "custom" => Custom,
+            "background" => Background,
+            "fill" => Fill,
+            "line" => Line,
+            "symbol" => Symbol,
+            "raster" => Raster,
+            "raster-particle" => RasterParticle,
+            "circle" => Circle,
+            "fill-extrusion" => FillExtrusion,
+            "heatmap" => Heatmap,
+            "hillshade" => Hillshade,
+            "sky" => Sky,
+            "model" => Model,
+            "slot" => Slot,
+            "clip" => Clip,
+        )
+        .map_err(D::Error::custom)
+    }
 }
 
 pub trait IntoLayer {
     fn into_layer(self) -> Layer;
 }
 
+/// Whether `layout` or `paint` has no property set, in which case it is left out
+/// so that a style read from JSON is written back the way it was.
+trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+fn is_empty<T: IsEmpty>(value: &T) -> bool {
+    value.is_empty()
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CustomLayer {
@@ -185,6 +250,16 @@ impl<T: fmt::Debug> fmt::Display for Expression<T> {
                 }
                 write!(f, "]")
             }
+            Expression::Object(map) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in map.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "\"{key}\": {value}")?;
+                }
+                write!(f, "}}")
+            }
             Expression::Enum(e) => write!(f, "{e:?}"),
         }
     }
@@ -194,10 +269,12 @@ impl<T: fmt::Debug> fmt::Display for Expression<T> {
 #[serde(rename_all = "camelCase")]
 pub struct BackgroundLayer {
     pub id: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: BackgroundLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: BackgroundPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for BackgroundLayer {
@@ -212,6 +289,7 @@ impl BackgroundLayer {
             id: id.into(),
             layout: BackgroundLayout::default(),
             paint: BackgroundPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -227,13 +305,41 @@ pub struct BackgroundPaint {
     pub background_opacity: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background_pattern: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for BackgroundPaint {
+    fn is_empty(&self) -> bool {
+        let BackgroundPaint {
+            background_color,
+            background_emissive_strength,
+            background_opacity,
+            background_pattern,
+            extra,
+        } = self;
+        background_color.is_none()
+            && background_emissive_strength.is_none()
+            && background_opacity.is_none()
+            && background_pattern.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct BackgroundLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for BackgroundLayout {
+    fn is_empty(&self) -> bool {
+        let BackgroundLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -252,10 +358,12 @@ pub struct FillLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: FillLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: FillPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for FillLayer {
@@ -276,6 +384,7 @@ impl FillLayer {
             slot: None,
             layout: FillLayout::default(),
             paint: FillPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -308,15 +417,55 @@ pub struct FillPaint {
     pub fill_translate: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fill_translate_anchor: Option<Expression<TranslateAnchor>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for FillPaint {
+    fn is_empty(&self) -> bool {
+        let FillPaint {
+            fill_antialias,
+            fill_color,
+            fill_emissive_strength,
+            fill_opacity,
+            fill_outline_color,
+            fill_pattern,
+            fill_translate,
+            fill_translate_anchor,
+            extra,
+        } = self;
+        fill_antialias.is_none()
+            && fill_color.is_none()
+            && fill_emissive_strength.is_none()
+            && fill_opacity.is_none()
+            && fill_outline_color.is_none()
+            && fill_pattern.is_none()
+            && fill_translate.is_none()
+            && fill_translate_anchor.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct FillLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub fill_sort_key: Option<u32>,
+    pub fill_sort_key: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for FillLayout {
+    fn is_empty(&self) -> bool {
+        let FillLayout {
+            fill_sort_key,
+            visibility,
+            extra,
+        } = self;
+        fill_sort_key.is_none() && visibility.is_none() && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -335,10 +484,12 @@ pub struct LineLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: LineLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: LinePaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for LineLayer {
@@ -359,6 +510,7 @@ impl LineLayer {
             slot: None,
             layout: LineLayout::default(),
             paint: LinePaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -413,6 +565,43 @@ pub struct LinePaint {
     pub line_trim_offset: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_width: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for LinePaint {
+    fn is_empty(&self) -> bool {
+        let LinePaint {
+            line_blur,
+            line_color,
+            line_dasharray,
+            line_emissive_strength,
+            line_gap_width,
+            line_gradient,
+            line_offset,
+            line_opacity,
+            line_pattern,
+            line_translate,
+            line_translate_anchor,
+            line_trim_offset,
+            line_width,
+            extra,
+        } = self;
+        line_blur.is_none()
+            && line_color.is_none()
+            && line_dasharray.is_none()
+            && line_emissive_strength.is_none()
+            && line_gap_width.is_none()
+            && line_gradient.is_none()
+            && line_offset.is_none()
+            && line_opacity.is_none()
+            && line_pattern.is_none()
+            && line_translate.is_none()
+            && line_translate_anchor.is_none()
+            && line_trim_offset.is_none()
+            && line_width.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -429,7 +618,30 @@ pub struct LineLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_sort_key: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for LineLayout {
+    fn is_empty(&self) -> bool {
+        let LineLayout {
+            line_cap,
+            line_join,
+            line_miter_limit,
+            line_round_limit,
+            line_sort_key,
+            visibility,
+            extra,
+        } = self;
+        line_cap.is_none()
+            && line_join.is_none()
+            && line_miter_limit.is_none()
+            && line_round_limit.is_none()
+            && line_sort_key.is_none()
+            && visibility.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -448,10 +660,12 @@ pub struct SymbolLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: SymbolLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: SymbolPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for SymbolLayer {
@@ -472,6 +686,7 @@ impl SymbolLayer {
             slot: None,
             layout: SymbolLayout::default(),
             paint: SymbolPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -599,6 +814,59 @@ pub struct SymbolPaint {
     pub text_translate: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text_translate_anchor: Option<Expression<TranslateAnchor>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for SymbolPaint {
+    fn is_empty(&self) -> bool {
+        let SymbolPaint {
+            icon_color,
+            icon_color_brightness_max,
+            icon_color_brightness_min,
+            icon_color_contrast,
+            icon_color_saturation,
+            icon_emissive_strength,
+            icon_halo_blur,
+            icon_halo_color,
+            icon_halo_width,
+            icon_image_cross_fade,
+            icon_opacity,
+            icon_translate,
+            icon_translate_anchor,
+            text_color,
+            text_emissive_strength,
+            text_halo_blur,
+            text_halo_color,
+            text_halo_width,
+            text_opacity,
+            text_translate,
+            text_translate_anchor,
+            extra,
+        } = self;
+        icon_color.is_none()
+            && icon_color_brightness_max.is_none()
+            && icon_color_brightness_min.is_none()
+            && icon_color_contrast.is_none()
+            && icon_color_saturation.is_none()
+            && icon_emissive_strength.is_none()
+            && icon_halo_blur.is_none()
+            && icon_halo_color.is_none()
+            && icon_halo_width.is_none()
+            && icon_image_cross_fade.is_none()
+            && icon_opacity.is_none()
+            && icon_translate.is_none()
+            && icon_translate_anchor.is_none()
+            && text_color.is_none()
+            && text_emissive_strength.is_none()
+            && text_halo_blur.is_none()
+            && text_halo_color.is_none()
+            && text_halo_width.is_none()
+            && text_opacity.is_none()
+            && text_translate.is_none()
+            && text_translate_anchor.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -689,7 +957,104 @@ pub struct SymbolLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text_writing_mode: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for SymbolLayout {
+    fn is_empty(&self) -> bool {
+        let SymbolLayout {
+            icon_allow_overlap,
+            icon_anchor,
+            icon_ignore_placement,
+            icon_image,
+            icon_keep_upright,
+            icon_offset,
+            icon_optional,
+            icon_padding,
+            icon_pitch_alignment,
+            icon_rotate,
+            icon_rotation_alignment,
+            icon_size,
+            icon_text_fit,
+            icon_text_fit_padding,
+            symbol_avoid_edges,
+            symbol_placement,
+            symbol_sort_key,
+            symbol_spacing,
+            symbol_z_elevate,
+            symbol_z_order,
+            text_allow_overlap,
+            text_anchor,
+            text_field,
+            text_font,
+            text_ignore_placement,
+            text_justify,
+            text_keep_upright,
+            text_letter_spacing,
+            text_line_height,
+            text_max_angle,
+            text_max_width,
+            text_offset,
+            text_optional,
+            text_padding,
+            text_pitch_alignment,
+            text_radial_offset,
+            text_rotate,
+            text_rotation_alignment,
+            text_size,
+            text_transform,
+            text_variable_anchor,
+            text_writing_mode,
+            visibility,
+            extra,
+        } = self;
+        icon_allow_overlap.is_none()
+            && icon_anchor.is_none()
+            && icon_ignore_placement.is_none()
+            && icon_image.is_none()
+            && icon_keep_upright.is_none()
+            && icon_offset.is_none()
+            && icon_optional.is_none()
+            && icon_padding.is_none()
+            && icon_pitch_alignment.is_none()
+            && icon_rotate.is_none()
+            && icon_rotation_alignment.is_none()
+            && icon_size.is_none()
+            && icon_text_fit.is_none()
+            && icon_text_fit_padding.is_none()
+            && symbol_avoid_edges.is_none()
+            && symbol_placement.is_none()
+            && symbol_sort_key.is_none()
+            && symbol_spacing.is_none()
+            && symbol_z_elevate.is_none()
+            && symbol_z_order.is_none()
+            && text_allow_overlap.is_none()
+            && text_anchor.is_none()
+            && text_field.is_none()
+            && text_font.is_none()
+            && text_ignore_placement.is_none()
+            && text_justify.is_none()
+            && text_keep_upright.is_none()
+            && text_letter_spacing.is_none()
+            && text_line_height.is_none()
+            && text_max_angle.is_none()
+            && text_max_width.is_none()
+            && text_offset.is_none()
+            && text_optional.is_none()
+            && text_padding.is_none()
+            && text_pitch_alignment.is_none()
+            && text_radial_offset.is_none()
+            && text_rotate.is_none()
+            && text_rotation_alignment.is_none()
+            && text_size.is_none()
+            && text_transform.is_none()
+            && text_variable_anchor.is_none()
+            && text_writing_mode.is_none()
+            && visibility.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -708,10 +1073,12 @@ pub struct RasterLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: RasterLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: RasterPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for RasterLayer {
@@ -732,6 +1099,7 @@ impl RasterLayer {
             slot: None,
             layout: RasterLayout::default(),
             paint: RasterPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -767,13 +1135,61 @@ pub struct RasterPaint {
     pub raster_resampling: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raster_saturation: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for RasterPaint {
+    fn is_empty(&self) -> bool {
+        let RasterPaint {
+            raster_array_band,
+            raster_brightness_max,
+            raster_brightness_min,
+            raster_color,
+            raster_color_mix,
+            raster_color_range,
+            raster_contrast,
+            raster_elevation,
+            raster_emissive_strength,
+            raster_fade_duration,
+            raster_hue_rotate,
+            raster_opacity,
+            raster_resampling,
+            raster_saturation,
+            extra,
+        } = self;
+        raster_array_band.is_none()
+            && raster_brightness_max.is_none()
+            && raster_brightness_min.is_none()
+            && raster_color.is_none()
+            && raster_color_mix.is_none()
+            && raster_color_range.is_none()
+            && raster_contrast.is_none()
+            && raster_elevation.is_none()
+            && raster_emissive_strength.is_none()
+            && raster_fade_duration.is_none()
+            && raster_hue_rotate.is_none()
+            && raster_opacity.is_none()
+            && raster_resampling.is_none()
+            && raster_saturation.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct RasterLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for RasterLayout {
+    fn is_empty(&self) -> bool {
+        let RasterLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -792,10 +1208,12 @@ pub struct RasterParticleLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: RasterParticleLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: RasterParticlePaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for RasterParticleLayer {
@@ -816,6 +1234,7 @@ impl RasterParticleLayer {
             slot: None,
             layout: RasterParticleLayout::default(),
             paint: RasterParticlePaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -837,13 +1256,47 @@ pub struct RasterParticlePaint {
     pub raster_particle_reset_rate_factor: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raster_particle_speed_factor: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for RasterParticlePaint {
+    fn is_empty(&self) -> bool {
+        let RasterParticlePaint {
+            raster_particle_array_band,
+            raster_particle_color,
+            raster_particle_count,
+            raster_particle_fade_opacity_factor,
+            raster_particle_max_speed,
+            raster_particle_reset_rate_factor,
+            raster_particle_speed_factor,
+            extra,
+        } = self;
+        raster_particle_array_band.is_none()
+            && raster_particle_color.is_none()
+            && raster_particle_count.is_none()
+            && raster_particle_fade_opacity_factor.is_none()
+            && raster_particle_max_speed.is_none()
+            && raster_particle_reset_rate_factor.is_none()
+            && raster_particle_speed_factor.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct RasterParticleLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for RasterParticleLayout {
+    fn is_empty(&self) -> bool {
+        let RasterParticleLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -862,10 +1315,12 @@ pub struct CircleLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: CircleLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: CirclePaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for CircleLayer {
@@ -886,6 +1341,7 @@ impl CircleLayer {
             slot: None,
             layout: CircleLayout::default(),
             paint: CirclePaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -935,6 +1391,41 @@ pub struct CirclePaint {
     pub circle_translate: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub circle_translate_anchor: Option<Expression<TranslateAnchor>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for CirclePaint {
+    fn is_empty(&self) -> bool {
+        let CirclePaint {
+            circle_blur,
+            circle_color,
+            circle_emissive_strength,
+            circle_opacity,
+            circle_pitch_alignment,
+            circle_pitch_scale,
+            circle_radius,
+            circle_stroke_color,
+            circle_stroke_opacity,
+            circle_stroke_width,
+            circle_translate,
+            circle_translate_anchor,
+            extra,
+        } = self;
+        circle_blur.is_none()
+            && circle_color.is_none()
+            && circle_emissive_strength.is_none()
+            && circle_opacity.is_none()
+            && circle_pitch_alignment.is_none()
+            && circle_pitch_scale.is_none()
+            && circle_radius.is_none()
+            && circle_stroke_color.is_none()
+            && circle_stroke_opacity.is_none()
+            && circle_stroke_width.is_none()
+            && circle_translate.is_none()
+            && circle_translate_anchor.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -943,7 +1434,20 @@ pub struct CircleLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub circle_sort_key: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for CircleLayout {
+    fn is_empty(&self) -> bool {
+        let CircleLayout {
+            circle_sort_key,
+            visibility,
+            extra,
+        } = self;
+        circle_sort_key.is_none() && visibility.is_none() && extra.is_empty()
+    }
 }
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -961,10 +1465,12 @@ pub struct FillExtrusionLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: FillExtrusionLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: FillExtrusionPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for FillExtrusionLayer {
@@ -985,6 +1491,7 @@ impl FillExtrusionLayer {
             slot: None,
             layout: FillExtrusionLayout::default(),
             paint: FillExtrusionPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -1032,13 +1539,73 @@ pub struct FillExtrusionPaint {
     pub fill_extrusion_vertical_gradient: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fill_extrusion_vertical_scale: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for FillExtrusionPaint {
+    fn is_empty(&self) -> bool {
+        let FillExtrusionPaint {
+            fill_extrusion_ambient_occlusion_ground_attenuation,
+            fill_extrusion_ambient_occlusion_ground_radius,
+            fill_extrusion_ambient_occlusion_wall_radius,
+            fill_extrusion_base,
+            fill_extrusion_color,
+            fill_extrusion_cutoff_fade_range,
+            fill_extrusion_emissive_strength,
+            fill_extrusion_flood_light_color,
+            fill_extrusion_flood_light_ground_attenuation,
+            fill_extrusion_flood_light_ground_radius,
+            fill_extrusion_flood_light_intensity,
+            fill_extrusion_flood_light_wall_radius,
+            fill_extrusion_height,
+            fill_extrusion_opacity,
+            fill_extrusion_pattern,
+            fill_extrusion_rounded_roof,
+            fill_extrusion_translate,
+            fill_extrusion_translate_anchor,
+            fill_extrusion_vertical_gradient,
+            fill_extrusion_vertical_scale,
+            extra,
+        } = self;
+        fill_extrusion_ambient_occlusion_ground_attenuation.is_none()
+            && fill_extrusion_ambient_occlusion_ground_radius.is_none()
+            && fill_extrusion_ambient_occlusion_wall_radius.is_none()
+            && fill_extrusion_base.is_none()
+            && fill_extrusion_color.is_none()
+            && fill_extrusion_cutoff_fade_range.is_none()
+            && fill_extrusion_emissive_strength.is_none()
+            && fill_extrusion_flood_light_color.is_none()
+            && fill_extrusion_flood_light_ground_attenuation.is_none()
+            && fill_extrusion_flood_light_ground_radius.is_none()
+            && fill_extrusion_flood_light_intensity.is_none()
+            && fill_extrusion_flood_light_wall_radius.is_none()
+            && fill_extrusion_height.is_none()
+            && fill_extrusion_opacity.is_none()
+            && fill_extrusion_pattern.is_none()
+            && fill_extrusion_rounded_roof.is_none()
+            && fill_extrusion_translate.is_none()
+            && fill_extrusion_translate_anchor.is_none()
+            && fill_extrusion_vertical_gradient.is_none()
+            && fill_extrusion_vertical_scale.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct FillExtrusionLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for FillExtrusionLayout {
+    fn is_empty(&self) -> bool {
+        let FillExtrusionLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -1057,10 +1624,12 @@ pub struct HeatmapLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: HeatmapLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: HeatmapPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for HeatmapLayer {
@@ -1081,6 +1650,7 @@ impl HeatmapLayer {
             slot: None,
             layout: HeatmapLayout::default(),
             paint: HeatmapPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -1098,13 +1668,43 @@ pub struct HeatmapPaint {
     pub heatmap_radius: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub heatmap_weight: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for HeatmapPaint {
+    fn is_empty(&self) -> bool {
+        let HeatmapPaint {
+            heatmap_color,
+            heatmap_intensity,
+            heatmap_opacity,
+            heatmap_radius,
+            heatmap_weight,
+            extra,
+        } = self;
+        heatmap_color.is_none()
+            && heatmap_intensity.is_none()
+            && heatmap_opacity.is_none()
+            && heatmap_radius.is_none()
+            && heatmap_weight.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct HeatmapLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for HeatmapLayout {
+    fn is_empty(&self) -> bool {
+        let HeatmapLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1122,10 +1722,12 @@ pub struct HillshadeLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: HillshadeLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: HillshadePaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for HillshadeLayer {
@@ -1146,6 +1748,7 @@ impl HillshadeLayer {
             slot: None,
             layout: HillshadeLayout::default(),
             paint: HillshadePaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -1167,13 +1770,47 @@ pub struct HillshadePaint {
     pub hillshade_illumination_direction: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hillshade_shadow_color: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for HillshadePaint {
+    fn is_empty(&self) -> bool {
+        let HillshadePaint {
+            hillshade_accent_color,
+            hillshade_emissive_strength,
+            hillshade_exaggeration,
+            hillshade_highlight_color,
+            hillshade_illumination_anchor,
+            hillshade_illumination_direction,
+            hillshade_shadow_color,
+            extra,
+        } = self;
+        hillshade_accent_color.is_none()
+            && hillshade_emissive_strength.is_none()
+            && hillshade_exaggeration.is_none()
+            && hillshade_highlight_color.is_none()
+            && hillshade_illumination_anchor.is_none()
+            && hillshade_illumination_direction.is_none()
+            && hillshade_shadow_color.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct HillshadeLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for HillshadeLayout {
+    fn is_empty(&self) -> bool {
+        let HillshadeLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1183,6 +1820,7 @@ pub struct SkyLayer {
     pub maxzoom: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minzoom: Option<f64>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub source: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<Expression<()>>,
@@ -1191,10 +1829,12 @@ pub struct SkyLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: SkyLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: SkyPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for SkyLayer {
@@ -1215,6 +1855,7 @@ impl SkyLayer {
             slot: None,
             layout: SkyLayout::default(),
             paint: SkyPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -1249,13 +1890,51 @@ pub struct SkyPaint {
     pub sky_opacity: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sky_type: Option<Expression<SkyType>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for SkyPaint {
+    fn is_empty(&self) -> bool {
+        let SkyPaint {
+            sky_atmosphere_color,
+            sky_atmosphere_halo_color,
+            sky_atmosphere_sun,
+            sky_atmosphere_sun_intensity,
+            sky_gradient,
+            sky_gradient_center,
+            sky_gradient_radius,
+            sky_opacity,
+            sky_type,
+            extra,
+        } = self;
+        sky_atmosphere_color.is_none()
+            && sky_atmosphere_halo_color.is_none()
+            && sky_atmosphere_sun.is_none()
+            && sky_atmosphere_sun_intensity.is_none()
+            && sky_gradient.is_none()
+            && sky_gradient_center.is_none()
+            && sky_gradient_radius.is_none()
+            && sky_opacity.is_none()
+            && sky_type.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct SkyLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for SkyLayout {
+    fn is_empty(&self) -> bool {
+        let SkyLayout { visibility, extra } = self;
+        visibility.is_none() && extra.is_empty()
+    }
 }
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1273,10 +1952,12 @@ pub struct ModelLayer {
     pub source_layer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub layout: ModelLayout,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_empty")]
     pub paint: ModelPaint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl IntoLayer for ModelLayer {
@@ -1297,6 +1978,7 @@ impl ModelLayer {
             slot: None,
             layout: ModelLayout::default(),
             paint: ModelPaint::default(),
+            extra: Default::default(),
         }
     }
 }
@@ -1341,6 +2023,45 @@ pub struct ModelPaint {
     pub model_translation: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_type: Option<Expression<ModelType>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for ModelPaint {
+    fn is_empty(&self) -> bool {
+        let ModelPaint {
+            model_ambient_occlusion_intensity,
+            model_cast_shadows,
+            model_color,
+            model_color_mix_intensity,
+            model_cutoff_fade_range,
+            model_emissive_strength,
+            model_height_based_emissive_strength_multiplier,
+            model_opacity,
+            model_receive_shadows,
+            model_rotation,
+            model_roughness,
+            model_scale,
+            model_translation,
+            model_type,
+            extra,
+        } = self;
+        model_ambient_occlusion_intensity.is_none()
+            && model_cast_shadows.is_none()
+            && model_color.is_none()
+            && model_color_mix_intensity.is_none()
+            && model_cutoff_fade_range.is_none()
+            && model_emissive_strength.is_none()
+            && model_height_based_emissive_strength_multiplier.is_none()
+            && model_opacity.is_none()
+            && model_receive_shadows.is_none()
+            && model_rotation.is_none()
+            && model_roughness.is_none()
+            && model_scale.is_none()
+            && model_translation.is_none()
+            && model_type.is_none()
+            && extra.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -1349,5 +2070,113 @@ pub struct ModelLayout {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_id: Option<Expression<()>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub visibility: Option<Visibility>,
+    pub visibility: Option<Expression<Visibility>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for ModelLayout {
+    fn is_empty(&self) -> bool {
+        let ModelLayout {
+            model_id,
+            visibility,
+            extra,
+        } = self;
+        model_id.is_none() && visibility.is_none() && extra.is_empty()
+    }
+}
+
+/// Placeholder layer marking where layers of an imported style are inserted.
+/// https://docs.mapbox.com/style-spec/reference/layers/#slot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotLayer {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IntoLayer for SlotLayer {
+    fn into_layer(self) -> Layer {
+        Layer::Slot(self)
+    }
+}
+
+impl SlotLayer {
+    pub fn new(id: impl Into<String>) -> SlotLayer {
+        SlotLayer {
+            id: id.into(),
+            slot: None,
+            extra: Default::default(),
+        }
+    }
+}
+
+/// https://docs.mapbox.com/style-spec/reference/layers/#clip
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipLayer {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "source-layer")]
+    pub source_layer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<String>,
+    #[serde(default, skip_serializing_if = "is_empty")]
+    pub layout: ClipLayout,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IntoLayer for ClipLayer {
+    fn into_layer(self) -> Layer {
+        Layer::Clip(self)
+    }
+}
+
+impl ClipLayer {
+    pub fn new(id: impl Into<String>, source: impl Into<String>) -> ClipLayer {
+        ClipLayer {
+            id: id.into(),
+            maxzoom: None,
+            minzoom: None,
+            source: source.into(),
+            filter: None,
+            source_layer: None,
+            slot: None,
+            layout: ClipLayout::default(),
+            extra: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClipLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clip_layer_types: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clip_layer_scope: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl IsEmpty for ClipLayout {
+    fn is_empty(&self) -> bool {
+        let ClipLayout {
+            clip_layer_types,
+            clip_layer_scope,
+            extra,
+        } = self;
+        clip_layer_types.is_none() && clip_layer_scope.is_none() && extra.is_empty()
+    }
 }
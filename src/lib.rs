@@ -1,7 +1,10 @@
 #![doc = include_str!("../README.md")]
 mod callback;
+pub mod easing;
 pub mod error;
 pub mod event;
+pub mod expr;
+pub mod feature;
 mod geometry;
 pub mod handler;
 mod id;
@@ -9,6 +12,7 @@ pub mod image;
 mod js;
 pub mod layer;
 pub mod marker;
+pub mod marker_manager;
 pub mod popup;
 pub mod source;
 pub mod style;
@@ -16,9 +20,9 @@ pub mod style;
 use enclose::enclose;
 use layer::IntoLayer;
 use log::*;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::HashMap,
     ops::DerefMut,
     rc::{Rc, Weak},
@@ -26,15 +30,20 @@ use std::{
 use wasm_bindgen::{prelude::*, JsCast};
 
 use callback::CallbackStore;
+pub use easing::Easing;
 pub use error::{Error, Result};
-use geometry::IntoQueryGeometry;
+pub use feature::{FeatureIdentifier, RenderedFeature};
+pub use geometry::{IntoQueryGeometry, QueryGeometry, ScreenPoint};
 pub use handler::BoxZoomHandler;
-pub use id::{CallbackId, MapListenerId, MarkerId};
+pub use id::{CallbackId, MapListenerId, MarkerId, PopupId};
 pub use image::{Image, ImageOptions};
 pub use layer::{BackgroundLayer, CustomLayer, Expression, FillLayer, Visibility};
 pub use marker::{Marker, MarkerEventListener, MarkerOptions};
-pub use popup::{Popup, PopupOptions};
-pub use source::GeoJsonSource;
+pub use marker_manager::{MarkerManager, MarkerManagerOptions};
+pub use popup::{Popup, PopupEventListener, PopupOptions};
+pub use source::{
+    GeoJsonSource, ImageSource, RasterTileSource, SourceHandle, VectorTileSource, VideoSource,
+};
 pub use style::{Source, Style, StyleOptions, StyleOrRef};
 
 #[wasm_bindgen]
@@ -176,7 +185,36 @@ impl std::fmt::Debug for LngLatBounds {
     }
 }
 
+impl Serialize for LngLatBounds {
+    fn serialize<S>(&self, ser: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let (sw, ne) = (self.get_south_west(), self.get_north_east());
+        [[sw.lng(), sw.lat()], [ne.lng(), ne.lat()]].serialize(ser)
+    }
+}
+
+impl<'de> Deserialize<'de> for LngLatBounds {
+    fn deserialize<D>(de: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let [[west, south], [east, north]] = <[[f64; 2]; 2]>::deserialize(de)?;
+        Ok(LngLatBounds::new(
+            LngLat::new(west, south),
+            LngLat::new(east, north),
+        ))
+    }
+}
+
 impl LngLatBounds {
+    pub fn new(sw: LngLat, ne: LngLat) -> LngLatBounds {
+        LngLatBounds {
+            inner: js::LngLatBounds::new(sw.inner, ne.inner),
+        }
+    }
+
     pub fn set_north_east(&self) -> LngLat {
         LngLat {
             inner: self.inner.getNorthEast(),
@@ -220,7 +258,8 @@ pub struct MapOptions {
     attribution_control: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     bearing: Option<i64>,
-    // TODO bounds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bounds: Option<LngLatBounds>,
     #[serde(skip_serializing_if = "Option::is_none")]
     box_zoom: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -244,6 +283,16 @@ pub struct MapOptions {
     drag_pan: Option<bool>,
     style: StyleOrRef,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_bounds: Option<LngLatBounds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_pitch: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_zoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_pitch: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_zoom: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     projection: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -269,6 +318,7 @@ impl MapOptions {
             antialias: None,
             attribution_control: None,
             bearing: None,
+            bounds: None,
             box_zoom: None,
             center: None,
             click_tolerance: None,
@@ -280,6 +330,11 @@ impl MapOptions {
             double_click_zoom: None,
             drag_pan: None,
             style: Default::default(),
+            max_bounds: None,
+            max_pitch: None,
+            max_zoom: None,
+            min_pitch: None,
+            min_zoom: None,
             projection: None,
             refresh_expired_tiles: None,
             render_world_copies: None,
@@ -320,6 +375,43 @@ impl MapOptions {
         self
     }
 
+    /// Initial bounds of the map, overrides `center` and `zoom`.
+    pub fn bounds(mut self, bounds: LngLatBounds) -> MapOptions {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Bounds the map is constrained to.
+    pub fn max_bounds(mut self, bounds: LngLatBounds) -> MapOptions {
+        self.max_bounds = Some(bounds);
+        self
+    }
+
+    pub fn min_zoom(mut self, zoom: f64) -> MapOptions {
+        self.min_zoom = Some(zoom);
+        self
+    }
+
+    pub fn max_zoom(mut self, zoom: f64) -> MapOptions {
+        self.max_zoom = Some(zoom);
+        self
+    }
+
+    pub fn min_pitch(mut self, pitch: f64) -> MapOptions {
+        self.min_pitch = Some(pitch);
+        self
+    }
+
+    pub fn max_pitch(mut self, pitch: f64) -> MapOptions {
+        self.max_pitch = Some(pitch);
+        self
+    }
+
+    pub fn render_world_copies(mut self, render_world_copies: bool) -> MapOptions {
+        self.render_world_copies = Some(render_world_copies);
+        self
+    }
+
     pub fn build(&self) -> JsValue {
         self.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
             .unwrap()
@@ -349,21 +441,21 @@ macro_rules! run_macro_with_events {
             (on_wheel, MapWheelEvent);
 
             // Movement
-            (on_movestart, DragEvent);
+            (on_movestart, MapEvent);
             (on_move, MapEvent);
-            (on_moveend, DragEvent);
-            (on_dragstart, MapBaseEvent);
-            (on_drag, DragEvent);
-            (on_dragend, DragEvent);
-            (on_zoomstart, MapBaseEvent);
-            (on_zoom, MapBaseEvent);
-            (on_zoomend, MapBaseEvent);
-            (on_rotatestart, MapBaseEvent);
-            (on_rotate, MapBaseEvent);
-            (on_rotateend, MapBaseEvent);
-            (on_pitchstart, MapBaseEvent);
-            (on_pitch, MapBaseEvent);
-            (on_pitchend, MapBaseEvent);
+            (on_moveend, MapEvent);
+            (on_dragstart, MapEvent);
+            (on_drag, MapEvent);
+            (on_dragend, MapEvent);
+            (on_zoomstart, MapEvent);
+            (on_zoom, MapEvent);
+            (on_zoomend, MapEvent);
+            (on_rotatestart, MapEvent);
+            (on_rotate, MapEvent);
+            (on_rotateend, MapEvent);
+            (on_pitchstart, MapEvent);
+            (on_pitch, MapEvent);
+            (on_pitchend, MapEvent);
             (on_boxzoomstart, MapBoxZoomEvent);
             (on_boxzoomend, MapBoxZoomEvent);
             (on_boxzoomcancel, MapBoxZoomEvent);
@@ -381,8 +473,10 @@ macro_rules! run_macro_with_events {
             (on_sourcedata, MapDataEvent);
             (on_dataloading, MapDataEvent);
             (on_styledataloading, MapDataEvent);
-            (on_sourcedataloading, MapBaseEvent);
-            (on_styleimagemissing, MapBaseEvent);
+            (on_sourcedataloading, MapDataEvent);
+            (on_styleimagemissing, StyleImageMissingEvent);
+
+            (on_error, MapErrorEvent);
         }
     };
 }
@@ -395,7 +489,6 @@ pub trait MapEventListener {
     $(
         fn $event(&mut self, map: Rc<Map>, e: event::$type) {}
     )*
-    fn on_error(&mut self, map: Rc<Map>, _message: String) {}
 }
 
     };
@@ -408,7 +501,8 @@ pub struct Handle {
     $(
         $event: Closure<dyn Fn(JsValue)>,
     )*
-    on_error: Closure<dyn Fn(JsValue)>,
+    /// Layer the closures were registered for with `on_layer`.
+    layer_id: Option<String>,
 }
 
     };
@@ -444,22 +538,23 @@ impl Handle {
                     }
                 )),
             )*
-            on_error: Closure::new(enclose!(
-                (map, f) move |value: JsValue| {
-                    web_sys::console::warn_2(&JsValue::from("on_error"), &value);
-
-                    let Some(map) = map.upgrade() else {
-                        warn!("Failed to get Map handle");
-                        return;
-                    };
+            layer_id: None,
+        }
+    }
 
-                    if let Ok(mut f) = f.try_borrow_mut() {
-                        f.deref_mut().on_error(map, value.as_string().unwrap());
-                    } else {
-                        error!("Could not borrow on_error handler. Handler is being called somewhere?");
-                    }
-                }
-            )),
+    /// Unregisters all the closures of the handle from the map.
+    fn unregister(&self, inner: &crate::js::Map) {
+        match &self.layer_id {
+            Some(layer_id) => {
+                $(
+                    inner.off_layer(stringify!($event).trim_start_matches("on_").into(), layer_id.clone(), &self.$event);
+                )*
+            }
+            None => {
+                $(
+                    inner.off(stringify!($event).trim_start_matches("on_").into(), &self.$event);
+                )*
+            }
         }
     }
 }
@@ -490,8 +585,6 @@ pub fn on<F: MapEventListener + 'static>(&self, f: F) -> Result<MapListenerId> {
         inner.on(stringify!($event).trim_start_matches("on_").into(), &handle.$event);
     )*
 
-    inner.on("error".into(), &handle.on_error);
-
     let id = MapListenerId(uuid::Uuid::new_v4());
     self.handles
         .try_borrow_mut()
@@ -509,7 +602,7 @@ macro_rules! impl_on_layer_method {
 
 /// Add a listener to a specified event type and layer.
 pub fn on_layer<F: MapEventListener + 'static>(&self, layer_id: &str, f: F) -> Result<MapListenerId> {
-    let handle = Handle::new(
+    let mut handle = Handle::new(
         self.weak_self
             .try_borrow()
             .map_err(|e| Error::Unexpected(format!("Could not borrow weak_self: {e}")))?
@@ -523,7 +616,7 @@ pub fn on_layer<F: MapEventListener + 'static>(&self, layer_id: &str, f: F) -> R
         inner.on_layer(stringify!($event).trim_start_matches("on_").into(), layer_id.into(), &handle.$event);
     )*
 
-    inner.on_layer("error".into(), layer_id.into(), &handle.on_error);
+    handle.layer_id = Some(layer_id.into());
 
     let id = MapListenerId(uuid::Uuid::new_v4());
     self.handles
@@ -537,11 +630,31 @@ pub fn on_layer<F: MapEventListener + 'static>(&self, layer_id: &str, f: F) -> R
     };
 }
 
+/// Closure of a single event registered with `on_event` and friends.
+pub struct EventHandle {
+    r#type: &'static str,
+    layer_id: Option<String>,
+    closure: Closure<dyn Fn(JsValue)>,
+}
+
+impl EventHandle {
+    fn unregister(&self, inner: &crate::js::Map) {
+        match &self.layer_id {
+            Some(layer_id) => inner.off_layer(self.r#type.into(), layer_id.clone(), &self.closure),
+            None => inner.off(self.r#type.into(), &self.closure),
+        }
+    }
+}
+
 pub struct Map {
     pub(crate) inner: crate::js::Map,
     pub(crate) handles: RefCell<HashMap<MapListenerId, Handle>>,
+    pub(crate) event_handles: RefCell<HashMap<MapListenerId, EventHandle>>,
     pub(crate) markers: RefCell<HashMap<MarkerId, Rc<Marker>>>,
+    pub(crate) popups: RefCell<HashMap<PopupId, Rc<Popup>>>,
     pub(crate) image_cbs: CallbackStore<dyn FnMut(JsValue, JsValue) + 'static>,
+    pub(crate) easings: RefCell<HashMap<CallbackId, easing::EasingClosure>>,
+    load_state: LoadState,
     pub(crate) weak_self: RefCell<Option<Weak<Map>>>,
 }
 
@@ -554,8 +667,12 @@ impl Map {
         let map = Rc::new(Map {
             inner,
             handles: RefCell::new(HashMap::new()),
+            event_handles: RefCell::new(HashMap::new()),
             markers: RefCell::new(HashMap::new()),
+            popups: RefCell::new(HashMap::new()),
             image_cbs: CallbackStore::new(),
+            easings: RefCell::new(HashMap::new()),
+            load_state: LoadState::default(),
             weak_self: RefCell::new(None),
         });
 
@@ -565,12 +682,196 @@ impl Map {
             .try_borrow_mut()
             .map_err(|e| Error::Unexpected(e.to_string()))? = Some(weak_self);
 
+        map.once_event::<event::kind::Load, _>(|map, _| map.load_state.fired.set(true))?;
+
         Ok(map)
     }
 
     run_macro_with_events!(impl_on_method);
     run_macro_with_events!(impl_on_layer_method);
 
+    /// Add a listener for a single kind of event, e.g. `map.on_event::<event::kind::Click>(..)`.
+    pub fn on_event<K, F>(&self, f: F) -> Result<MapListenerId>
+    where
+        K: event::EventKind,
+        F: FnMut(Rc<Map>, K::Event) + 'static,
+    {
+        self.add_event_handle::<K, F>(None, false, f)
+    }
+
+    /// Add a listener for a single kind of event on a specified layer.
+    pub fn on_layer_event<K, F>(&self, layer_id: &str, f: F) -> Result<MapListenerId>
+    where
+        K: event::EventKind,
+        F: FnMut(Rc<Map>, K::Event) + 'static,
+    {
+        self.add_event_handle::<K, F>(Some(layer_id.into()), false, f)
+    }
+
+    /// Add a listener that is called only the next time the event fires, then removed.
+    pub fn once_event<K, F>(&self, f: F) -> Result<MapListenerId>
+    where
+        K: event::EventKind,
+        F: FnOnce(Rc<Map>, K::Event) + 'static,
+    {
+        let f = RefCell::new(Some(f));
+        self.add_event_handle::<K, _>(None, true, move |map, e| {
+            if let Some(f) = f.take() {
+                f(map, e);
+            }
+        })
+    }
+
+    /// Resolves once the map emitted `load`, immediately if it already did, even while
+    /// tiles are loading again.
+    pub async fn wait_for_load(&self) -> Result<()> {
+        if !self.load_state.must_wait(self.loaded()) {
+            return Ok(());
+        }
+        self.once(event::kind::Load).await.map(|_| ())
+    }
+
+    /// Returns a future resolving with the next event of the kind, e.g.
+    /// `map.once(event::kind::Idle).await`.
+    ///
+    /// The listener is registered right away, so the future can be created before starting an
+    /// animation and awaited after.
+    pub fn once<K: event::EventKind>(
+        &self,
+        _kind: K,
+    ) -> impl std::future::Future<Output = Result<K::Event>> {
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        let registered = self.once_event::<K, _>(move |_, e| {
+            let _ = sender.send(e);
+        });
+
+        async move {
+            registered?;
+            receiver
+                .await
+                .map_err(|_| Error::Unexpected(format!("Listener for {} was removed", K::NAME)))
+        }
+    }
+
+    /// Returns a stream of every event of the kind, e.g. `map.events(event::kind::MouseMove)`.
+    pub fn events<K: event::EventKind>(&self, _kind: K) -> Result<event::EventStream<K::Event>> {
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let id = self.on_event::<K, _>(move |_, e| {
+            let _ = sender.unbounded_send(e);
+        })?;
+        let map = self
+            .weak_self
+            .try_borrow()
+            .map_err(|e| Error::Unexpected(format!("Could not borrow weak_self: {e}")))?
+            .clone()
+            .ok_or_else(|| Error::Unexpected("Weak reference is missing".to_string()))?;
+
+        Ok(event::EventStream { receiver, map, id })
+    }
+
+    fn add_event_handle<K, F>(
+        &self,
+        layer_id: Option<String>,
+        once: bool,
+        f: F,
+    ) -> Result<MapListenerId>
+    where
+        K: event::EventKind,
+        F: FnMut(Rc<Map>, K::Event) + 'static,
+    {
+        let map = self
+            .weak_self
+            .try_borrow()
+            .map_err(|e| Error::Unexpected(format!("Could not borrow weak_self: {e}")))?
+            .clone()
+            .ok_or_else(|| Error::Unexpected("Weak reference is missing".to_string()))?;
+        let id = MapListenerId(uuid::Uuid::new_v4());
+        let f = RefCell::new(f);
+
+        let closure = Closure::<dyn Fn(JsValue)>::new(move |value: JsValue| {
+            let Some(map) = map.upgrade() else {
+                warn!("Failed to get Map handle");
+                return;
+            };
+
+            match K::Event::try_from(value) {
+                Ok(e) => {
+                    if let Ok(mut f) = f.try_borrow_mut() {
+                        f(map.clone(), e);
+                    } else {
+                        error!(
+                            "Could not borrow {} handler. Handler is being called somewhere?",
+                            K::NAME
+                        );
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to deserialize Event: {e}");
+                }
+            }
+
+            if once {
+                // The closure can't be dropped while it is running, so let it go once the
+                // current JS task is done.
+                wasm_bindgen_futures::spawn_local(async move {
+                    if let Ok(mut handles) = map.event_handles.try_borrow_mut() {
+                        handles.remove(&id);
+                    }
+                });
+            }
+        });
+
+        match (&layer_id, once) {
+            (Some(layer_id), false) => {
+                self.inner
+                    .on_layer(K::NAME.into(), layer_id.clone(), &closure)
+            }
+            (Some(layer_id), true) => {
+                self.inner
+                    .once_layer(K::NAME.into(), layer_id.clone(), &closure)
+            }
+            (None, false) => self.inner.on(K::NAME.into(), &closure),
+            (None, true) => self.inner.once(K::NAME.into(), &closure),
+        }
+
+        self.event_handles
+            .try_borrow_mut()
+            .map_err(|e| Error::Unexpected(format!("Could not get lock for handles: {e}")))?
+            .insert(
+                id,
+                EventHandle {
+                    r#type: K::NAME,
+                    layer_id,
+                    closure,
+                },
+            );
+
+        Ok(id)
+    }
+
+    /// Remove a listener added with `on`, `on_layer`, `on_event` and friends, dropping its closures.
+    pub fn off(&self, id: MapListenerId) -> Result<()> {
+        let handle = self
+            .handles
+            .try_borrow_mut()
+            .map_err(|e| Error::Unexpected(format!("Could not get lock for handles: {e}")))?
+            .remove(&id);
+        if let Some(handle) = handle {
+            handle.unregister(&self.inner);
+        }
+
+        let event_handle = self
+            .event_handles
+            .try_borrow_mut()
+            .map_err(|e| Error::Unexpected(format!("Could not get lock for handles: {e}")))?
+            .remove(&id);
+        if let Some(event_handle) = event_handle {
+            event_handle.unregister(&self.inner);
+        }
+
+        Ok(())
+    }
+
     pub fn add_marker(&self, marker: Rc<Marker>) -> MarkerId {
         let id = MarkerId(uuid::Uuid::new_v4());
         marker.add_to(self);
@@ -595,6 +896,51 @@ impl Map {
             .expect("Could not get lock for markders")
             .remove(id);
     }
+
+    /// Opens the popup on the map. The map keeps the popup alive until it is closed or removed.
+    pub fn add_popup(&self, popup: Rc<Popup>) -> PopupId {
+        let id = popup.id().unwrap_or_else(|| PopupId(uuid::Uuid::new_v4()));
+        popup.add_to_map(self, id);
+        self.popups
+            .try_borrow_mut()
+            .expect("Could not get lock for popups")
+            .insert(id, popup);
+
+        id
+    }
+
+    /// Closes the popup and stops tracking it.
+    pub fn remove_popup(&self, id: &PopupId) -> Option<Rc<Popup>> {
+        let popup = self
+            .popups
+            .try_borrow_mut()
+            .expect("Could not get lock for popups")
+            .remove(id)?;
+        popup.remove();
+        Some(popup)
+    }
+
+    /// Popups currently open on the map.
+    pub fn popups(&self) -> Vec<(PopupId, Rc<Popup>)> {
+        self.popups
+            .try_borrow()
+            .expect("Could not get lock for popups")
+            .iter()
+            .map(|(id, popup)| (*id, popup.clone()))
+            .collect()
+    }
+
+    pub fn close_all_popups(&self) {
+        let popups: Vec<_> = self
+            .popups
+            .try_borrow_mut()
+            .expect("Could not get lock for popups")
+            .drain()
+            .collect();
+        for (_, popup) in popups {
+            popup.remove();
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -620,6 +966,19 @@ pub struct QueryFeatureOptions {
     pub validate: Option<bool>,
 }
 
+/// https://docs.mapbox.com/mapbox-gl-js/api/map/#map#querysourcefeatures
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuerySourceFeatureOptions {
+    /// Required for vector tile sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_layer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate: Option<bool>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CameraOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -636,12 +995,39 @@ pub struct CameraOptions {
     pub zoom: Option<f64>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Padding in pixels from each edge of the map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct PaddingOptions {
-    pub bottom: Option<LngLat>,
-    pub left: Option<LngLat>,
-    pub right: Option<LngLat>,
-    pub top: Option<LngLat>,
+    #[serde(default)]
+    pub bottom: f64,
+    #[serde(default)]
+    pub left: f64,
+    #[serde(default)]
+    pub right: f64,
+    #[serde(default)]
+    pub top: f64,
+}
+
+impl PaddingOptions {
+    pub fn new(top: f64, right: f64, bottom: f64, left: f64) -> PaddingOptions {
+        PaddingOptions {
+            bottom,
+            left,
+            right,
+            top,
+        }
+    }
+
+    /// Same padding on every side.
+    pub fn uniform(padding: f64) -> PaddingOptions {
+        PaddingOptions::new(padding, padding, padding, padding)
+    }
+}
+
+impl From<f64> for PaddingOptions {
+    fn from(padding: f64) -> Self {
+        PaddingOptions::uniform(padding)
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -653,24 +1039,66 @@ pub struct AnimationOptions {
     pub curve: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
-    // TODO
-    //#[serde(skip_serializing_if = "Option::is_none")]
-    //pub easing: Option<xxx>,
+    /// Kept alive by the map while the animation may be running.
+    #[serde(skip)]
+    pub easing: Option<Easing>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub essential: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_duration: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_zoom: Option<f64>,
-    // TODO
-    //#[serde(skip_serializing_if = "Option::is_none")]
-    //pub offset: Option<xxx>,
+    /// Offset of the target center in pixels as `(x, y)`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<(f64, f64)>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preloading_only: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub screen_speed: Option<f64>,
 }
 
+impl AnimationOptions {
+    pub fn new() -> AnimationOptions {
+        AnimationOptions::default()
+    }
+
+    pub(crate) fn build(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(self).unwrap()
+    }
+}
+
+/// https://docs.mapbox.com/mapbox-gl-js/api/map/#map#fitbounds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FitBoundsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearing: Option<f64>,
+    /// Use `easeTo` instead of `flyTo` for the transition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linear: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_zoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<PaddingOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f64>,
+    /// `offset` of the animation options offsets the center of the bounds.
+    #[serde(flatten)]
+    pub animation_options: AnimationOptions,
+}
+
+impl FitBoundsOptions {
+    pub fn new() -> FitBoundsOptions {
+        FitBoundsOptions::default()
+    }
+
+    pub(crate) fn build(&self) -> JsValue {
+        // This serializer is always needed when using serde's 'flatten' directive to js
+        let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        self.serialize(&ser).unwrap()
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CameraAnimationOptions {
     #[serde(flatten)]
@@ -693,6 +1121,57 @@ impl Map {
         self.inner.getMinZoom()
     }
 
+    /// Sets the minimum zoom, `None` resets it to the default.
+    pub fn set_min_zoom(&self, zoom: Option<f64>) {
+        self.inner.setMinZoom(zoom)
+    }
+
+    pub fn get_max_zoom(&self) -> f64 {
+        self.inner.getMaxZoom()
+    }
+
+    /// Sets the maximum zoom, `None` resets it to the default.
+    pub fn set_max_zoom(&self, zoom: Option<f64>) {
+        self.inner.setMaxZoom(zoom)
+    }
+
+    pub fn get_min_pitch(&self) -> f64 {
+        self.inner.getMinPitch()
+    }
+
+    /// Sets the minimum pitch, `None` resets it to the default.
+    pub fn set_min_pitch(&self, pitch: Option<f64>) {
+        self.inner.setMinPitch(pitch)
+    }
+
+    pub fn get_max_pitch(&self) -> f64 {
+        self.inner.getMaxPitch()
+    }
+
+    /// Sets the maximum pitch, `None` resets it to the default.
+    pub fn set_max_pitch(&self, pitch: Option<f64>) {
+        self.inner.setMaxPitch(pitch)
+    }
+
+    pub fn get_max_bounds(&self) -> Option<LngLatBounds> {
+        self.inner
+            .getMaxBounds()
+            .map(|inner| LngLatBounds { inner })
+    }
+
+    /// Constrains the map to the bounds, `None` removes the constraint.
+    pub fn set_max_bounds(&self, bounds: Option<&LngLatBounds>) {
+        self.inner.setMaxBounds(bounds.map(|b| &b.inner))
+    }
+
+    pub fn get_render_world_copies(&self) -> bool {
+        self.inner.getRenderWorldCopies()
+    }
+
+    pub fn set_render_world_copies(&self, render_world_copies: bool) {
+        self.inner.setRenderWorldCopies(render_world_copies)
+    }
+
     pub fn get_center(&self) -> LngLat {
         self.inner.getCenter().into()
     }
@@ -877,98 +1356,104 @@ impl Map {
         serde_wasm_bindgen::from_value(expr).map_err(Error::from)
     }
 
+    /// Sets the state of a feature, merged into its existing state. `state` must serialize to an
+    /// object, e.g. a struct or a map.
+    pub fn set_feature_state<S: Serialize>(
+        &self,
+        feature: &FeatureIdentifier,
+        state: &S,
+    ) -> Result<()> {
+        let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        self.inner
+            .setFeatureState(feature.serialize(&ser)?, state.serialize(&ser)?);
+        Ok(())
+    }
+
+    pub fn get_feature_state<S: DeserializeOwned>(&self, feature: &FeatureIdentifier) -> Result<S> {
+        let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        let state = self.inner.getFeatureState(feature.serialize(&ser)?);
+        serde_wasm_bindgen::from_value(state).map_err(Error::from)
+    }
+
+    /// Removes the `key` from the state of a feature, or its whole state if `key` is `None`.
+    pub fn remove_feature_state(
+        &self,
+        feature: &FeatureIdentifier,
+        key: Option<String>,
+    ) -> Result<()> {
+        let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        self.inner.removeFeatureState(feature.serialize(&ser)?, key);
+        Ok(())
+    }
+
     pub fn query_rendered_features<G: IntoQueryGeometry>(
         &self,
         geometry: Option<G>,
         options: QueryFeatureOptions,
     ) -> Result<Vec<geojson::Feature>> {
-        // It seems GeoJSON returned from mapbox-gl-js queryRenderedFeatures contains
-        // byte array, which causes deserialize error with geojson crate. geojson
-        // crate internally deserialize all the properties into Map.
-        //
-        // As a workaround, use an intermediate "Feature" struct to deserialize from
-        // JsValue using serde, then convert it to geojson::Feature.
-        #[derive(Debug, serde::Deserialize)]
-        #[serde(untagged)]
-        enum Id {
-            String(String),
-            Number(serde_json::Number),
-        }
-
-        #[derive(Debug, serde::Deserialize)]
-        struct Feature {
-            bbox: Option<geojson::Bbox>,
-            geometry: Option<geojson::Geometry>,
-            id: Option<Id>,
-            properties: Option<geojson::JsonObject>,
-            foreign_members: Option<geojson::JsonObject>,
-        }
+        let geometry = geometry.map(|g| g.into_query_geometry(self)).transpose()?;
         let res = self.inner.queryRenderedFeatures(
-            serde_wasm_bindgen::to_value(&geometry.map(|g| g.into_query_geometry().into_vec()))?,
+            serde_wasm_bindgen::to_value(&geometry)?,
             serde_wasm_bindgen::to_value(&options)?,
         );
 
-        let features: Vec<Feature> = serde_wasm_bindgen::from_value(res)?;
-
-        features
-            .into_iter()
-            .map(|f| {
-                Ok(geojson::Feature {
-                    bbox: f.bbox,
-                    geometry: f.geometry,
-                    id: f.id.map(|id| match id {
-                        Id::String(s) => geojson::feature::Id::String(s),
-                        Id::Number(n) => geojson::feature::Id::Number(n),
-                    }),
-                    properties: f.properties,
-                    foreign_members: f.foreign_members,
-                })
-            })
-            .collect()
+        feature::features_from_js(res)
     }
 
-    pub fn add_vector_source(&self, id: impl Into<String>, url: impl Into<String>) -> Result<()> {
-        #[derive(Serialize)]
-        struct VectorSource {
-            r#type: String,
-            url: String,
-        }
-
-        self.inner.addSource(
-            id.into(),
-            serde_wasm_bindgen::to_value(&VectorSource {
-                r#type: "vector".into(),
-                url: url.into(),
-            })?,
-        );
+    /// Returns the features of a source that match `options`, including the ones outside of the
+    /// viewport but in loaded tiles.
+    pub fn query_source_features(
+        &self,
+        source_id: impl Into<String>,
+        options: QuerySourceFeatureOptions,
+    ) -> Result<Vec<geojson::Feature>> {
+        let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        let res = self
+            .inner
+            .querySourceFeatures(source_id.into(), options.serialize(&ser)?);
 
-        Ok(())
+        feature::features_from_js(res)
     }
 
-    pub fn add_geojson_source(&self, id: impl Into<String>, data: geojson::GeoJson) -> Result<()> {
+    /// Adds a source of any type to the map's style.
+    ///
+    /// https://docs.mapbox.com/mapbox-gl-js/api/map/#map#addsource
+    pub fn add_source(&self, id: impl Into<String>, source: impl Into<Source>) -> Result<()> {
         let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
-        let data = source::GeoJsonSourceSpec::new(data)
-            .serialize(&ser)
-            .map_err(|e| Error::BadGeoJson(e.to_string()))?;
+        let source = source.into().serialize(&ser)?;
 
-        self.inner.addSource(id.into(), data);
+        self.inner.addSource(id.into(), source);
 
         Ok(())
     }
 
+    pub fn add_vector_source(&self, id: impl Into<String>, url: impl Into<String>) -> Result<()> {
+        self.add_source(id, style::VectorSourceSpec::new(url))
+    }
+
+    pub fn add_geojson_source(&self, id: impl Into<String>, data: geojson::GeoJson) -> Result<()> {
+        self.add_source(id, style::GeoJsonSourceSpec::new(data))
+            .map_err(|e| Error::BadGeoJson(e.to_string()))
+    }
+
     pub fn add_geojson_source_from_url(
         &self,
         id: impl Into<String>,
         data: impl Into<String>,
     ) -> Result<()> {
-        let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
-        let data = source::GeoJsonSourceSpec::new(data.into())
-            .serialize(&ser)
-            .map_err(|e| Error::BadGeoJson(e.to_string()))?;
+        self.add_source(id, style::GeoJsonSourceSpec::new(data.into()))
+            .map_err(|e| Error::BadGeoJson(e.to_string()))
+    }
 
-        self.inner.addSource(id.into(), data);
+    /// Returns a handle to the source, typed after the source's `type`.
+    pub fn get_source(&self, id: impl Into<String>) -> Option<source::SourceHandle> {
+        let source = self.inner.getSource(id.into());
 
-        Ok(())
+        if !source.is_undefined() {
+            Some(source::SourceHandle::from_js(source))
+        } else {
+            None
+        }
     }
 
     pub fn get_geojson_source(&self, id: impl Into<String>) -> Option<source::GeoJsonSource> {
@@ -1008,6 +1493,22 @@ impl Map {
         self.inner.getZoom()
     }
 
+    /// Converts a geographic position to pixels in the map container.
+    pub fn project(&self, lnglat: &LngLat) -> Result<ScreenPoint> {
+        let point = self.inner.project(&lnglat.inner);
+        Ok(serde_wasm_bindgen::from_value(point)?)
+    }
+
+    /// Converts pixels in the map container to a geographic position.
+    pub fn unproject(&self, point: impl Into<ScreenPoint>) -> LngLat {
+        let [x, y] = point.into().to_array();
+        LngLat {
+            inner: self
+                .inner
+                .unproject(js_sys::Array::of2(&x.into(), &y.into()).into()),
+        }
+    }
+
     pub fn jump_to(&self, options: CameraOptions) {
         self.inner
             .jumpTo(serde_wasm_bindgen::to_value(&options).unwrap());
@@ -1016,23 +1517,213 @@ impl Map {
     pub fn ease_to(&self, camera_options: CameraOptions, animation_options: AnimationOptions) {
         // This serializer is always needed when using serde's 'flatten' directive to js
         let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        let easing = animation_options.easing.clone();
         let options = CameraAnimationOptions {
             camera_options,
             animation_options,
         };
 
-        self.inner.easeTo(options.serialize(&ser).unwrap());
+        self.animate(options.serialize(&ser).unwrap(), easing.as_ref(), |o| {
+            self.inner.easeTo(o)
+        });
     }
 
     pub fn fly_to(&self, camera_options: CameraOptions, animation_options: AnimationOptions) {
         // This serializer is always needed when using serde's 'flatten' directive to js
         let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+        let easing = animation_options.easing.clone();
         let options = CameraAnimationOptions {
             camera_options,
             animation_options,
         };
 
-        self.inner
-            .flyTo(options.serialize(&ser).unwrap(), JsValue::null());
+        self.animate(options.serialize(&ser).unwrap(), easing.as_ref(), |o| {
+            self.inner.flyTo(o, JsValue::null())
+        });
+    }
+
+    pub fn set_center(&self, center: LngLat) {
+        self.inner.setCenter(&center.inner, JsValue::undefined());
+    }
+
+    pub fn set_zoom(&self, zoom: f64) {
+        self.inner.setZoom(zoom, JsValue::undefined());
+    }
+
+    pub fn get_bearing(&self) -> f64 {
+        self.inner.getBearing()
+    }
+
+    pub fn set_bearing(&self, bearing: f64) {
+        self.inner.setBearing(bearing, JsValue::undefined());
+    }
+
+    pub fn get_pitch(&self) -> f64 {
+        self.inner.getPitch()
+    }
+
+    pub fn set_pitch(&self, pitch: f64) {
+        self.inner.setPitch(pitch, JsValue::undefined());
+    }
+
+    pub fn zoom_to(&self, zoom: f64, options: AnimationOptions) {
+        self.with_easing(&options, |o| {
+            self.inner.zoomTo(zoom, o, JsValue::undefined())
+        });
+    }
+
+    pub fn zoom_in(&self, options: AnimationOptions) {
+        self.with_easing(&options, |o| self.inner.zoomIn(o, JsValue::undefined()));
+    }
+
+    pub fn zoom_out(&self, options: AnimationOptions) {
+        self.with_easing(&options, |o| self.inner.zoomOut(o, JsValue::undefined()));
+    }
+
+    pub fn rotate_to(&self, bearing: f64, options: AnimationOptions) {
+        self.with_easing(&options, |o| {
+            self.inner.rotateTo(bearing, o, JsValue::undefined())
+        });
+    }
+
+    /// Rotates the map to a bearing of 0.
+    pub fn reset_north(&self, options: AnimationOptions) {
+        self.with_easing(&options, |o| self.inner.resetNorth(o, JsValue::undefined()));
+    }
+
+    /// Rotates and pitches the map to a bearing and pitch of 0.
+    pub fn reset_north_pitch(&self, options: AnimationOptions) {
+        self.with_easing(&options, |o| {
+            self.inner.resetNorthPitch(o, JsValue::undefined())
+        });
+    }
+
+    /// Snaps the bearing to 0 if it is within the `bearingSnap` threshold.
+    pub fn snap_to_north(&self, options: AnimationOptions) {
+        self.with_easing(&options, |o| {
+            self.inner.snapToNorth(o, JsValue::undefined())
+        });
+    }
+
+    /// Pans the map by `(x, y)` pixels.
+    pub fn pan_by(&self, offset: (f64, f64), options: AnimationOptions) {
+        let offset = js_sys::Array::of2(&offset.0.into(), &offset.1.into());
+        self.with_easing(&options, |o| {
+            self.inner.panBy(offset.into(), o, JsValue::undefined())
+        });
+    }
+
+    /// Pans and zooms the map to contain the bounds.
+    pub fn fit_bounds(&self, bounds: &LngLatBounds, options: FitBoundsOptions) {
+        self.with_fit_bounds_options(&options, |o| {
+            self.inner.fitBounds(&bounds.inner, o, JsValue::undefined())
+        });
+    }
+
+    /// Pans, rotates and zooms the map to fit the box between two points in pixels,
+    /// after rotating the map to `bearing`.
+    pub fn fit_screen_coordinates(
+        &self,
+        p0: impl Into<ScreenPoint>,
+        p1: impl Into<ScreenPoint>,
+        bearing: f64,
+        options: FitBoundsOptions,
+    ) {
+        let [x0, y0] = p0.into().to_array();
+        let [x1, y1] = p1.into().to_array();
+        self.with_fit_bounds_options(&options, |o| {
+            self.inner.fitScreenCoordinates(
+                js_sys::Array::of2(&x0.into(), &y0.into()).into(),
+                js_sys::Array::of2(&x1.into(), &y1.into()).into(),
+                bearing,
+                o,
+                JsValue::undefined(),
+            )
+        });
+    }
+
+    /// Camera that fits the bounds, or `None` if the bounds can't fit in the viewport.
+    /// Only the camera related fields of `options` are used.
+    pub fn camera_for_bounds(
+        &self,
+        bounds: &LngLatBounds,
+        options: FitBoundsOptions,
+    ) -> Result<Option<CameraOptions>> {
+        let camera = self.inner.cameraForBounds(&bounds.inner, options.build());
+        if camera.is_undefined() || camera.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_wasm_bindgen::from_value(camera)?))
+    }
+
+    /// Stops any ongoing camera animation.
+    pub fn stop(&self) {
+        self.inner.stop();
+    }
+
+    fn with_easing(&self, options: &AnimationOptions, start: impl FnOnce(JsValue)) {
+        self.animate(options.build(), options.easing.as_ref(), start)
+    }
+
+    fn with_fit_bounds_options(&self, options: &FitBoundsOptions, start: impl FnOnce(JsValue)) {
+        let easing = options.animation_options.easing.as_ref();
+        self.animate(options.build(), easing, start)
+    }
+
+    /// Starts an animation with `easing` set on the serialized options as a JS function.
+    /// The function is dropped on the `moveend` ending the animation.
+    fn animate(&self, options: JsValue, easing: Option<&Easing>, start: impl FnOnce(JsValue)) {
+        let Some(easing) = easing else {
+            return start(options);
+        };
+        let closure = easing.to_closure();
+        if let Err(e) = js_sys::Reflect::set(&options, &"easing".into(), closure.as_ref()) {
+            warn!("Failed to set easing: {e:?}");
+        }
+        start(options);
+
+        // The animation already ended, e.g. with a zero duration.
+        if !self.inner.isEasing() {
+            return;
+        }
+        let id = CallbackId(uuid::Uuid::new_v4());
+        self.easings.borrow_mut().insert(id, closure);
+        // Registered after starting, so the `moveend` of an animation stopped by this one
+        // doesn't drop the function.
+        let res = self.once_event::<event::kind::MoveEnd, _>(move |map, _| {
+            map.easings.borrow_mut().remove(&id);
+        });
+        if let Err(e) = res {
+            warn!("Failed to listen to moveend for easing: {e:?}");
+        }
+    }
+}
+
+/// Whether the map emitted `load`. It fires once, while `loaded()` is false again
+/// whenever tiles or sources are loading.
+#[derive(Default)]
+struct LoadState {
+    fired: Cell<bool>,
+}
+
+impl LoadState {
+    fn must_wait(&self, loaded: bool) -> bool {
+        !self.fired.get() && !loaded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_for_load_until_load_fired() {
+        let state = LoadState::default();
+        assert!(state.must_wait(false));
+        assert!(!state.must_wait(true));
+
+        state.fired.set(true);
+        // Loaded but busy, e.g. fetching tiles during a pan.
+        assert!(!state.must_wait(false));
     }
 }
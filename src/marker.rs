@@ -50,13 +50,20 @@ impl_handler_for_marker! {
     (on_dragstart, JsValue),
     (on_drag, JsValue),
     (on_dragend, JsValue),
+    (on_click, JsValue),
+    (on_mouseenter, JsValue),
+    (on_mouseleave, JsValue),
 }
 
 #[allow(unused_variables)]
 pub trait MarkerEventListener {
-    fn on_dragstart(&mut self, map: Rc<Marker>, e: event::MapBaseEvent) {}
-    fn on_drag(&mut self, map: Rc<Marker>, e: event::DragEvent) {}
-    fn on_dragend(&mut self, map: Rc<Marker>, e: event::DragEvent) {}
+    fn on_dragstart(&mut self, map: Rc<Marker>, e: event::MarkerDragEvent) {}
+    fn on_drag(&mut self, map: Rc<Marker>, e: event::MarkerDragEvent) {}
+    fn on_dragend(&mut self, map: Rc<Marker>, e: event::MarkerDragEvent) {}
+    /// Called on DOM events of the marker element.
+    fn on_click(&mut self, map: Rc<Marker>, e: event::MarkerMouseEvent) {}
+    fn on_mouseenter(&mut self, map: Rc<Marker>, e: event::MarkerMouseEvent) {}
+    fn on_mouseleave(&mut self, map: Rc<Marker>, e: event::MarkerMouseEvent) {}
 }
 
 struct NoopListener;
@@ -67,13 +74,52 @@ pub struct MarkerHandle {
     on_dragstart: Closure<dyn Fn(JsValue)>,
     on_drag: Closure<dyn Fn(JsValue)>,
     on_dragend: Closure<dyn Fn(JsValue)>,
+    on_click: Closure<dyn Fn(JsValue)>,
+    on_mouseenter: Closure<dyn Fn(JsValue)>,
+    on_mouseleave: Closure<dyn Fn(JsValue)>,
+}
+
+/// Part of the element placed closest to the coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Anchor {
+    #[default]
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// How the marker is aligned when the map is rotated or pitched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Alignment {
+    Map,
+    Viewport,
+    #[default]
+    Auto,
+}
+
+impl Alignment {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Alignment::Map => "map",
+            Alignment::Viewport => "viewport",
+            Alignment::Auto => "auto",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct MarkerOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub anchor: Option<String>,
+    pub anchor: Option<Anchor>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub click_tolerance: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -82,15 +128,19 @@ pub struct MarkerOptions {
     pub draggable: Option<bool>,
     #[serde(skip)]
     pub element: Option<web_sys::HtmlElement>,
-    //pub offset?
+    /// Offset in pixels as `(x, y)`, negative values go left and up.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pitch_alignment: Option<String>,
+    pub offset: Option<(f64, f64)>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rotation: Option<u64>,
+    pub pitch_alignment: Option<Alignment>,
+    /// Rotation angle in degrees, clockwise.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rotation_alignment: Option<String>,
+    pub rotation: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub scale: Option<u64>,
+    pub rotation_alignment: Option<Alignment>,
+    /// Scale of the default marker, ignored with a custom `element`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f64>,
 }
 
 impl MarkerOptions {
@@ -113,8 +163,8 @@ impl MarkerOptions {
 
 pub struct Marker {
     inner: js::Marker,
-    lnglat: LngLat,
     handle: RefCell<Option<MarkerHandle>>,
+    popup: RefCell<Option<Rc<Popup>>>,
 }
 
 impl Marker {
@@ -130,10 +180,12 @@ impl Marker {
     where
         F: MarkerEventListener + 'static,
     {
+        let inner = js::Marker::maker_new(options.build());
+        inner.setLngLat(&lnglat.inner);
         let marker = Rc::new(Marker {
-            inner: js::Marker::maker_new(options.build()),
-            lnglat,
+            inner,
             handle: RefCell::new(None),
+            popup: RefCell::new(None),
         });
 
         let handle = MarkerHandle::new(Rc::downgrade(&marker), f);
@@ -141,13 +193,25 @@ impl Marker {
         inner.Marker_on("dragstart".into(), &handle.on_dragstart);
         inner.Marker_on("drag".into(), &handle.on_drag);
         inner.Marker_on("dragend".into(), &handle.on_dragend);
+
+        let element = inner.Marker_getElement();
+        for (r#type, closure) in [
+            ("click", &handle.on_click),
+            ("mouseenter", &handle.on_mouseenter),
+            ("mouseleave", &handle.on_mouseleave),
+        ] {
+            if let Err(e) =
+                element.add_event_listener_with_callback(r#type, closure.as_ref().unchecked_ref())
+            {
+                warn!("Failed to listen to {type} on marker element: {e:?}");
+            }
+        }
         marker.handle.borrow_mut().replace(handle);
 
         marker
     }
 
     pub(crate) fn add_to(&self, map: &Map) {
-        self.inner.setLngLat(&self.lnglat.inner);
         self.inner.addTo(&map.inner)
     }
 
@@ -163,4 +227,64 @@ impl Marker {
     pub fn set_lnglat(&self, pos: &LngLat) {
         self.inner.setLngLat(&pos.inner)
     }
+
+    pub fn get_element(&self) -> web_sys::HtmlElement {
+        self.inner.Marker_getElement()
+    }
+
+    /// Sets the offset in pixels as `(x, y)`.
+    pub fn set_offset(&self, offset: (f64, f64)) {
+        let offset = js_sys::Array::of2(&offset.0.into(), &offset.1.into());
+        self.inner.Marker_setOffset(offset.into())
+    }
+
+    /// Sets the rotation angle in degrees, clockwise.
+    pub fn set_rotation(&self, rotation: f64) {
+        self.inner.Marker_setRotation(rotation)
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        self.inner.Marker_getRotation()
+    }
+
+    pub fn set_draggable(&self, draggable: bool) {
+        self.inner.Marker_setDraggable(draggable)
+    }
+
+    pub fn is_draggable(&self) -> bool {
+        self.inner.Marker_isDraggable()
+    }
+
+    pub fn set_pitch_alignment(&self, alignment: Alignment) {
+        self.inner.Marker_setPitchAlignment(alignment.as_str())
+    }
+
+    pub fn set_rotation_alignment(&self, alignment: Alignment) {
+        self.inner.Marker_setRotationAlignment(alignment.as_str())
+    }
+
+    /// Binds a popup that opens when the marker is clicked. The marker keeps the popup alive.
+    pub fn set_popup(&self, popup: Rc<Popup>) {
+        self.inner.Marker_setPopup(Some(&popup.inner));
+        self.popup.borrow_mut().replace(popup);
+    }
+
+    /// Unbinds the popup set with [`set_popup`](Self::set_popup) and returns it.
+    pub fn remove_popup(&self) -> Option<Rc<Popup>> {
+        self.inner.Marker_setPopup(None);
+        self.popup.borrow_mut().take()
+    }
+
+    /// Opens or closes the popup bound to the marker.
+    pub fn toggle_popup(&self) {
+        self.inner.Marker_togglePopup()
+    }
+
+    pub fn add_class_name(&self, class_name: &str) {
+        self.inner.Marker_addClassName(class_name)
+    }
+
+    pub fn remove_class_name(&self, class_name: &str) {
+        self.inner.Marker_removeClassName(class_name)
+    }
 }
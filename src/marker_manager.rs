@@ -0,0 +1,606 @@
+//! Viewport culling and clustering for large sets of markers.
+//!
+//! Markers added with [`Map::add_marker`](crate::Map::add_marker) stay attached to the
+//! map wherever they are. [`MarkerManager`] keeps its markers in a spatial index and
+//! only attaches those within the viewport on every `moveend`.
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::{Rc, Weak},
+};
+
+use crate::{event, LngLat, Map, MapListenerId, Marker, MarkerId, MarkerOptions, Result};
+
+/// Size of the spatial index cells in degrees.
+const CELL_DEGREES: f64 = 1.0;
+
+/// Number of index cells around the globe.
+const COLUMNS: i32 = (360.0 / CELL_DEGREES) as i32;
+
+/// Size of a mapbox-gl-js world at zoom 0 in pixels.
+const TILE_SIZE: f64 = 512.0;
+
+/// Markers grouped together by [`ClusterOptions`].
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    /// Average position of the grouped markers as `(lng, lat)`.
+    pub lng_lat: (f64, f64),
+    pub markers: Vec<MarkerId>,
+}
+
+impl Cluster {
+    pub fn count(&self) -> usize {
+        self.markers.len()
+    }
+}
+
+pub struct ClusterOptions {
+    /// Markers closer than this many pixels to the first marker of a cluster are grouped
+    /// with it, so the markers of a cluster may be up to twice as far from each other.
+    pub radius: f64,
+    /// Markers are no longer grouped above this zoom.
+    pub max_zoom: f64,
+    /// Minimum number of markers to form a cluster.
+    pub min_points: usize,
+    /// Creates the element shown for a cluster.
+    pub element: Box<dyn Fn(&Cluster) -> web_sys::HtmlElement>,
+}
+
+impl ClusterOptions {
+    pub fn new<F>(element: F) -> ClusterOptions
+    where
+        F: Fn(&Cluster) -> web_sys::HtmlElement + 'static,
+    {
+        ClusterOptions {
+            radius: 50.0,
+            max_zoom: 14.0,
+            min_points: 2,
+            element: Box::new(element),
+        }
+    }
+}
+
+pub struct MarkerManagerOptions {
+    /// Markers this many pixels outside of the viewport are attached too.
+    pub margin: f64,
+    pub cluster: Option<ClusterOptions>,
+}
+
+impl Default for MarkerManagerOptions {
+    fn default() -> Self {
+        MarkerManagerOptions {
+            margin: 100.0,
+            cluster: None,
+        }
+    }
+}
+
+impl MarkerManagerOptions {
+    pub fn new() -> MarkerManagerOptions {
+        MarkerManagerOptions::default()
+    }
+}
+
+struct Entry {
+    marker: Rc<Marker>,
+    attached: bool,
+}
+
+struct State {
+    options: MarkerManagerOptions,
+    entries: HashMap<MarkerId, Entry>,
+    index: Index,
+    clusters: Vec<Rc<Marker>>,
+}
+
+/// Keeps a spatial index of markers and attaches only the visible ones to the map.
+///
+/// Markers are placed when the map emits `moveend`. Call [`refresh`](Self::refresh)
+/// after adding, removing or moving markers to update the map right away.
+pub struct MarkerManager {
+    map: Weak<Map>,
+    state: Rc<RefCell<State>>,
+    listener: MapListenerId,
+}
+
+impl MarkerManager {
+    pub fn new(map: &Rc<Map>, options: MarkerManagerOptions) -> Result<MarkerManager> {
+        let state = Rc::new(RefCell::new(State {
+            options,
+            entries: HashMap::new(),
+            index: Index::default(),
+            clusters: vec![],
+        }));
+
+        let weak_state = Rc::downgrade(&state);
+        let listener = map.on_event::<event::kind::MoveEnd, _>(move |map, _| {
+            if let Some(state) = weak_state.upgrade() {
+                state.borrow_mut().refresh(&map);
+            }
+        })?;
+
+        Ok(MarkerManager {
+            map: Rc::downgrade(map),
+            state,
+            listener,
+        })
+    }
+
+    pub fn add(&self, marker: Rc<Marker>) -> MarkerId {
+        let id = MarkerId(uuid::Uuid::new_v4());
+        let lnglat = marker.get_lnglat();
+
+        let mut state = self.state.borrow_mut();
+        state.index.insert(id, (lnglat.lng(), lnglat.lat()));
+        state.entries.insert(
+            id,
+            Entry {
+                marker,
+                attached: false,
+            },
+        );
+
+        id
+    }
+
+    pub fn extend(&self, markers: impl IntoIterator<Item = Rc<Marker>>) -> Vec<MarkerId> {
+        markers.into_iter().map(|m| self.add(m)).collect()
+    }
+
+    pub fn remove(&self, id: &MarkerId) -> Option<Rc<Marker>> {
+        let mut state = self.state.borrow_mut();
+        let entry = state.entries.remove(id)?;
+        state.index.remove(id);
+        if entry.attached {
+            entry.marker.remove();
+        }
+
+        Some(entry.marker)
+    }
+
+    /// Re-indexes a marker after it was moved with [`Marker::set_lnglat`] or dragged.
+    ///
+    /// [`refresh`](Self::refresh) re-indexes all the markers as well.
+    pub fn update(&self, id: &MarkerId) {
+        let mut state = self.state.borrow_mut();
+        let Some(entry) = state.entries.get(id) else {
+            return;
+        };
+        let lnglat = entry.marker.get_lnglat();
+        state.index.insert(*id, (lnglat.lng(), lnglat.lat()));
+    }
+
+    pub fn get(&self, id: &MarkerId) -> Option<Rc<Marker>> {
+        self.state
+            .borrow()
+            .entries
+            .get(id)
+            .map(|e| e.marker.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.borrow().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.borrow().entries.is_empty()
+    }
+
+    /// Removes all the markers from the manager and the map.
+    pub fn clear(&self) {
+        let mut state = self.state.borrow_mut();
+        state.detach_all();
+        state.entries.clear();
+        state.index = Index::default();
+    }
+
+    /// Re-indexes the markers at their current positions, then attaches those within
+    /// the current viewport and detaches the others.
+    pub fn refresh(&self) {
+        if let Some(map) = self.map.upgrade() {
+            self.state.borrow_mut().refresh(&map);
+        }
+    }
+}
+
+impl Drop for MarkerManager {
+    fn drop(&mut self) {
+        if let Some(map) = self.map.upgrade() {
+            let _ = map.off(self.listener);
+        }
+        if let Ok(mut state) = self.state.try_borrow_mut() {
+            state.detach_all();
+        }
+    }
+}
+
+impl State {
+    fn refresh(&mut self, map: &Map) {
+        let Ok(bounds) = map.get_bounds() else {
+            return;
+        };
+        let zoom = map.get_zoom();
+        let world = TILE_SIZE * 2f64.powf(zoom);
+
+        // Markers may have been dragged or moved since they were indexed.
+        for (id, entry) in &self.entries {
+            let lnglat = entry.marker.get_lnglat();
+            self.index.insert(*id, (lnglat.lng(), lnglat.lat()));
+        }
+
+        let (sw, ne) = (bounds.get_south_west(), bounds.get_north_east());
+        let viewport = Bounds {
+            west: sw.lng(),
+            south: sw.lat(),
+            east: ne.lng(),
+            north: ne.lat(),
+        };
+        let visible = self
+            .index
+            .query(viewport.expand(self.options.margin, world));
+
+        for marker in self.clusters.drain(..) {
+            marker.remove();
+        }
+
+        let (singles, clusters) = match &self.options.cluster {
+            Some(cluster) if zoom < cluster.max_zoom => group(&self.index, visible, cluster, world),
+            _ => (visible, vec![]),
+        };
+
+        for (id, entry) in self.entries.iter_mut() {
+            let wanted = singles.contains(id);
+            if wanted && !entry.attached {
+                entry.marker.add_to(map);
+            } else if !wanted && entry.attached {
+                entry.marker.remove();
+            }
+            entry.attached = wanted;
+        }
+
+        if let Some(options) = &self.options.cluster {
+            for cluster in clusters {
+                let mut marker_options = MarkerOptions::new();
+                marker_options.element = Some((options.element)(&cluster));
+                let (lng, lat) = cluster.lng_lat;
+                let marker = Marker::with_options(LngLat::new(lng, lat), marker_options);
+                marker.add_to(map);
+                self.clusters.push(marker);
+            }
+        }
+    }
+
+    fn detach_all(&mut self) {
+        for marker in self.clusters.drain(..) {
+            marker.remove();
+        }
+        for entry in self.entries.values_mut() {
+            if entry.attached {
+                entry.marker.remove();
+                entry.attached = false;
+            }
+        }
+    }
+}
+
+/// Area in degrees, `east` may be greater than 180 when crossing the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bounds {
+    west: f64,
+    south: f64,
+    east: f64,
+    north: f64,
+}
+
+impl Bounds {
+    /// Grows the bounds by `margin` pixels on a world of `world` pixels.
+    fn expand(self, margin: f64, world: f64) -> Bounds {
+        let margin = margin / world;
+        Bounds {
+            west: self.west - margin * 360.0,
+            south: unproject_y(project_y(self.south) + margin),
+            east: self.east + margin * 360.0,
+            north: unproject_y(project_y(self.north) - margin),
+        }
+    }
+}
+
+/// Marker positions as `(lng, lat)` bucketed in cells of [`CELL_DEGREES`].
+#[derive(Default)]
+struct Index {
+    positions: HashMap<MarkerId, (f64, f64)>,
+    cells: HashMap<(i32, i32), HashSet<MarkerId>>,
+}
+
+impl Index {
+    fn insert(&mut self, id: MarkerId, position: (f64, f64)) {
+        match self.positions.get(&id) {
+            Some(old) if *old == position => return,
+            Some(_) => self.remove(&id),
+            None => {}
+        }
+        self.positions.insert(id, position);
+        self.cells.entry(cell(position)).or_default().insert(id);
+    }
+
+    fn remove(&mut self, id: &MarkerId) {
+        let Some(position) = self.positions.remove(id) else {
+            return;
+        };
+        let key = cell(position);
+        if let Some(ids) = self.cells.get_mut(&key) {
+            ids.remove(id);
+            if ids.is_empty() {
+                self.cells.remove(&key);
+            }
+        }
+    }
+
+    fn query(&self, bounds: Bounds) -> HashSet<MarkerId> {
+        let Bounds {
+            west,
+            south,
+            east,
+            north,
+        } = bounds;
+        let all_lng = east - west >= 360.0;
+        let contains_lng = |lng: f64| all_lng || west + (lng - west).rem_euclid(360.0) <= east;
+        let (cell_west, cell_south) = cell((west, south));
+        let (cell_east, cell_north) = cell((east, north));
+        // Columns from west to east, wrapping around the antimeridian.
+        let columns = if all_lng {
+            COLUMNS
+        } else {
+            (cell_east - cell_west).rem_euclid(COLUMNS) + 1
+        };
+
+        let mut found = HashSet::new();
+        for column in 0..columns {
+            let x = (cell_west + column + COLUMNS / 2).rem_euclid(COLUMNS) - COLUMNS / 2;
+            for y in cell_south..=cell_north {
+                for id in self.cells.get(&(x, y)).into_iter().flatten() {
+                    let (lng, lat) = self.positions[id];
+                    if lat >= south && lat <= north && contains_lng(lng) {
+                        found.insert(*id);
+                    }
+                }
+            }
+        }
+
+        found
+    }
+}
+
+/// Splits the visible markers into single markers and clusters.
+///
+/// Markers are visited from west to east and each one not grouped yet collects the
+/// ungrouped markers within the radius, looked up in the neighbouring cells of a pixel
+/// grid of the radius so that markers on both sides of a cell edge are grouped too.
+fn group(
+    index: &Index,
+    visible: HashSet<MarkerId>,
+    options: &ClusterOptions,
+    world: f64,
+) -> (HashSet<MarkerId>, Vec<Cluster>) {
+    let radius = options.radius;
+    let mut points: Vec<(f64, f64, MarkerId)> = visible
+        .into_iter()
+        .map(|id| {
+            let (lng, lat) = index.positions[&id];
+            let lng = (lng + 180.0).rem_euclid(360.0) - 180.0;
+            ((lng + 180.0) / 360.0 * world, project_y(lat) * world, id)
+        })
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+    let key = |x: f64, y: f64| ((x / radius).floor() as i64, (y / radius).floor() as i64);
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, (x, y, _)) in points.iter().enumerate() {
+        grid.entry(key(*x, *y)).or_default().push(i);
+    }
+
+    let mut grouped = vec![false; points.len()];
+    let mut singles = HashSet::new();
+    let mut clusters = vec![];
+    for (i, (x, y, id)) in points.iter().enumerate() {
+        if grouped[i] {
+            continue;
+        }
+        let (kx, ky) = key(*x, *y);
+        let mut members = vec![];
+        for nx in kx - 1..=kx + 1 {
+            for ny in ky - 1..=ky + 1 {
+                for &j in grid.get(&(nx, ny)).into_iter().flatten() {
+                    let (px, py, _) = points[j];
+                    if !grouped[j] && (px - x).hypot(py - y) <= radius {
+                        members.push(j);
+                    }
+                }
+            }
+        }
+
+        if members.len() < options.min_points.max(2) {
+            grouped[i] = true;
+            singles.insert(*id);
+            continue;
+        }
+        let count = members.len() as f64;
+        let (lng, lat) = members.iter().fold((0.0, 0.0), |(lng, lat), &j| {
+            grouped[j] = true;
+            let (m_lng, m_lat) = index.positions[&points[j].2];
+            (lng + m_lng, lat + m_lat)
+        });
+        members.sort_unstable();
+        clusters.push(Cluster {
+            lng_lat: (lng / count, lat / count),
+            markers: members.into_iter().map(|j| points[j].2).collect(),
+        });
+    }
+
+    (singles, clusters)
+}
+
+fn cell((lng, lat): (f64, f64)) -> (i32, i32) {
+    let lng = (lng + 180.0).rem_euclid(360.0) - 180.0;
+    (
+        (lng / CELL_DEGREES).floor() as i32,
+        (lat / CELL_DEGREES).floor() as i32,
+    )
+}
+
+/// Latitude to Web Mercator y in `[0, 1]` from north to south.
+fn project_y(lat: f64) -> f64 {
+    let lat = lat.clamp(-85.051129, 85.051129).to_radians();
+    (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / std::f64::consts::PI) / 2.0
+}
+
+fn unproject_y(y: f64) -> f64 {
+    let n = std::f64::consts::PI * (1.0 - 2.0 * y.clamp(0.0, 1.0));
+    n.sinh().atan().to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(positions: &[(f64, f64)]) -> (Index, Vec<MarkerId>) {
+        let mut index = Index::default();
+        let ids = positions
+            .iter()
+            .map(|p| {
+                let id = MarkerId(uuid::Uuid::new_v4());
+                index.insert(id, *p);
+                id
+            })
+            .collect();
+        (index, ids)
+    }
+
+    fn cluster_options(radius: f64, min_points: usize) -> ClusterOptions {
+        ClusterOptions {
+            radius,
+            min_points,
+            ..ClusterOptions::new(|_| unreachable!())
+        }
+    }
+
+    /// Longitude at `x` pixels from the west edge of a world of `world` pixels.
+    fn lng_at(x: f64, world: f64) -> f64 {
+        x / world * 360.0 - 180.0
+    }
+
+    #[test]
+    fn query_across_antimeridian() {
+        let (index, ids) = index(&[(179.5, 0.0), (-179.5, 0.0), (0.0, 0.0)]);
+        let expected: HashSet<_> = [ids[0], ids[1]].into();
+
+        for (west, east) in [(170.0, 190.0), (-190.0, -170.0)] {
+            let bounds = Bounds {
+                west,
+                south: -10.0,
+                east,
+                north: 10.0,
+            };
+            assert_eq!(index.query(bounds), expected, "{west}..{east}");
+        }
+    }
+
+    #[test]
+    fn query_skips_markers_outside_the_range() {
+        let (index, ids) = index(&[
+            (10.5, 10.5),
+            (10.5, 30.5),
+            (30.5, 10.5),
+            (-169.5, 10.5),
+            (11.9, 11.9),
+            (12.1, 10.5),
+        ]);
+        let bounds = Bounds {
+            west: 10.0,
+            south: 10.0,
+            east: 12.0,
+            north: 12.0,
+        };
+        assert_eq!(index.query(bounds), [ids[0], ids[4]].into());
+
+        let world = Bounds {
+            west: -200.0,
+            south: 10.0,
+            east: 200.0,
+            north: 11.0,
+        };
+        assert_eq!(index.query(world), [ids[0], ids[2], ids[3], ids[5]].into());
+    }
+
+    #[test]
+    fn query_after_move() {
+        let (mut index, ids) = index(&[(0.5, 0.5)]);
+        let bounds = Bounds {
+            west: 0.0,
+            south: 0.0,
+            east: 1.0,
+            north: 1.0,
+        };
+        assert_eq!(index.query(bounds), [ids[0]].into());
+
+        index.insert(ids[0], (50.5, 0.5));
+        assert!(index.query(bounds).is_empty());
+        assert_eq!(index.cells.len(), 1);
+    }
+
+    #[test]
+    fn margin_expansion() {
+        let world = TILE_SIZE * 4.0;
+        let viewport = Bounds {
+            west: -10.0,
+            south: -10.0,
+            east: 10.0,
+            north: 10.0,
+        };
+        let expanded = viewport.expand(world / 360.0, world);
+        assert!((expanded.west - -11.0).abs() < 1e-9);
+        assert!((expanded.east - 11.0).abs() < 1e-9);
+        assert!(expanded.south < -10.0 && expanded.north > 10.0);
+        assert!((expanded.south + expanded.north).abs() < 1e-9);
+
+        let (index, ids) = index(&[(10.5, 0.0), (0.0, 10.5), (12.0, 0.0)]);
+        assert!(index.query(viewport).is_empty());
+        assert_eq!(index.query(expanded), [ids[0], ids[1]].into());
+    }
+
+    #[test]
+    fn min_points() {
+        let world = TILE_SIZE;
+        let near: Vec<_> = (0..3)
+            .map(|i| (lng_at(100.0 + i as f64 * 10.0, world), 0.0))
+            .collect();
+        let (index, ids) = index(&near);
+        let visible: HashSet<_> = ids.iter().copied().collect();
+
+        let (singles, clusters) = group(&index, visible.clone(), &cluster_options(50.0, 3), world);
+        assert!(singles.is_empty());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].count(), 3);
+
+        let (singles, clusters) = group(&index, visible.clone(), &cluster_options(50.0, 4), world);
+        assert_eq!(singles, visible);
+        assert!(clusters.is_empty());
+    }
+
+    #[test]
+    fn group_across_cell_edge() {
+        let world = TILE_SIZE;
+        let (index, ids) = index(&[
+            (lng_at(49.5, world), 0.0),
+            (lng_at(50.5, world), 0.0),
+            (lng_at(200.0, world), 0.0),
+        ]);
+        let visible: HashSet<_> = ids.iter().copied().collect();
+
+        let (singles, clusters) = group(&index, visible, &cluster_options(50.0, 2), world);
+        assert_eq!(singles, [ids[2]].into());
+        assert_eq!(clusters.len(), 1);
+        let members: HashSet<_> = clusters[0].markers.iter().copied().collect();
+        assert_eq!(members, [ids[0], ids[1]].into());
+    }
+}
@@ -1,11 +1,35 @@
+use log::*;
 use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    ops::DerefMut,
+    rc::{Rc, Weak},
+};
 use wasm_bindgen::prelude::*;
 
+use crate::{marker::Anchor, LngLat, Map, PopupId};
+
 #[derive(Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct PopupOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<Anchor>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub class_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_button: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_on_click: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_on_move: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_after_open: Option<bool>,
+    /// CSS max-width, e.g. `"240px"` or `"none"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<String>,
+    /// Offset in pixels as `(x, y)`, negative values go left and up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<(f64, f64)>,
 }
 
 impl PopupOptions {
@@ -20,23 +44,162 @@ impl PopupOptions {
     }
 }
 
+#[allow(unused_variables)]
+pub trait PopupEventListener {
+    fn on_open(&mut self, popup: Rc<Popup>) {}
+    fn on_close(&mut self, popup: Rc<Popup>) {}
+}
+
+struct NoopListener;
+
+impl PopupEventListener for NoopListener {}
+
+pub struct PopupHandle {
+    on_open: Closure<dyn Fn(JsValue)>,
+    on_close: Closure<dyn Fn(JsValue)>,
+}
+
+impl PopupHandle {
+    pub fn new<F: PopupEventListener + 'static>(popup: Weak<Popup>, f: F) -> PopupHandle {
+        let f = Rc::new(RefCell::new(f));
+
+        let on_open = {
+            let (popup, f) = (popup.clone(), f.clone());
+            Closure::new(move |_: JsValue| {
+                let Some(popup) = popup.upgrade() else {
+                    warn!("Failed to get a popup handle");
+                    return;
+                };
+                match f.try_borrow_mut() {
+                    Ok(mut f) => f.deref_mut().on_open(popup),
+                    Err(_) => error!("Popup event handler is being called somewhere."),
+                }
+            })
+        };
+
+        let on_close = Closure::new(move |_: JsValue| {
+            let Some(popup) = popup.upgrade() else {
+                warn!("Failed to get a popup handle");
+                return;
+            };
+            match f.try_borrow_mut() {
+                Ok(mut f) => f.deref_mut().on_close(popup.clone()),
+                Err(_) => error!("Popup event handler is being called somewhere."),
+            }
+            // The map may hold the last reference to the popup, which owns this closure,
+            // so stop tracking it once the handler has returned.
+            wasm_bindgen_futures::spawn_local(async move { popup.untrack() });
+        });
+
+        PopupHandle { on_open, on_close }
+    }
+}
+
 pub struct Popup {
-    inner: crate::js::Popup,
-    latlng: crate::LngLat,
+    pub(crate) inner: crate::js::Popup,
+    handle: RefCell<Option<PopupHandle>>,
+    map: RefCell<Weak<Map>>,
+    id: Cell<Option<PopupId>>,
 }
 
 impl Popup {
-    pub fn new(latlng: crate::LngLat, options: PopupOptions) -> Popup {
+    pub fn new(latlng: LngLat, options: PopupOptions) -> Rc<Popup> {
+        Self::with_listener(latlng, options, NoopListener {})
+    }
+
+    pub fn with_listener<F>(latlng: LngLat, options: PopupOptions, f: F) -> Rc<Popup>
+    where
+        F: PopupEventListener + 'static,
+    {
         let inner = crate::js::Popup::Popup_new(options.build());
-        Popup { inner, latlng }
+        inner.Popup_setLngLat(&latlng.inner);
+        let popup = Rc::new(Popup {
+            inner,
+            handle: RefCell::new(None),
+            map: RefCell::new(Weak::new()),
+            id: Cell::new(None),
+        });
+
+        let handle = PopupHandle::new(Rc::downgrade(&popup), f);
+        popup.inner.Popup_on("open".into(), &handle.on_open);
+        popup.inner.Popup_on("close".into(), &handle.on_close);
+        popup.handle.borrow_mut().replace(handle);
+
+        popup
+    }
+
+    /// Opens the popup on the map, same as [`Map::add_popup`].
+    pub fn add_to(self: &Rc<Self>, map: &Map) -> PopupId {
+        map.add_popup(self.clone())
     }
 
-    pub fn add_to(&self, map: &crate::Map) {
-        self.inner.Popup_setLngLat(&self.latlng.inner);
+    pub(crate) fn add_to_map(&self, map: &Map, id: PopupId) {
+        if let Some(weak_map) = map.weak_self.borrow().as_ref() {
+            *self.map.borrow_mut() = weak_map.clone();
+        }
+        self.id.set(Some(id));
         self.inner.Popup_addTo(&map.inner)
     }
 
+    /// ID given by the map while the popup is tracked by it.
+    pub fn id(&self) -> Option<PopupId> {
+        self.id.get()
+    }
+
+    /// Closes the popup and removes it from the map.
+    pub fn remove(&self) {
+        self.inner.Popup_remove()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.inner.Popup_isOpen()
+    }
+
+    pub fn get_lng_lat(&self) -> LngLat {
+        LngLat {
+            inner: self.inner.Popup_getLngLat(),
+        }
+    }
+
+    pub fn set_lng_lat(&self, latlng: &LngLat) {
+        self.inner.Popup_setLngLat(&latlng.inner);
+    }
+
+    /// Makes the popup follow the cursor instead of a coordinate.
+    pub fn track_pointer(&self) {
+        self.inner.Popup_trackPointer();
+    }
+
     pub fn set_html(&self, html: impl Into<String>) {
         self.inner.Popup_setHTML(html.into());
     }
+
+    pub fn set_text(&self, text: &str) {
+        self.inner.Popup_setText(text);
+    }
+
+    pub fn set_dom_content(&self, node: &web_sys::Node) {
+        self.inner.Popup_setDOMContent(node);
+    }
+
+    pub fn set_max_width(&self, max_width: &str) {
+        self.inner.Popup_setMaxWidth(max_width);
+    }
+
+    fn untrack(&self) {
+        if self.is_open() {
+            return;
+        }
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        let Some(map) = self.map.replace(Weak::new()).upgrade() else {
+            return;
+        };
+        let Ok(mut popups) = map.popups.try_borrow_mut() else {
+            warn!("Could not get lock for popups");
+            return;
+        };
+        popups.remove(&id);
+    }
 }
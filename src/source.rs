@@ -1,24 +1,181 @@
-use crate::Result;
+use crate::{style::Coordinates, Error, Result};
+use geojson::{feature::Id, Feature, FeatureCollection};
 use serde::Serialize;
+use std::collections::HashMap;
+use wasm_bindgen::{prelude::*, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
 
-#[derive(Debug, Clone, Serialize)]
-pub struct GeoJsonSourceSpec<S: Serialize> {
-    pub r#type: String,
-    pub data: S,
+pub use crate::style::GeoJsonSourceSpec;
+
+/// Handle of a source retrieved by [`Map::get_source`](crate::Map::get_source).
+pub enum SourceHandle {
+    GeoJson(GeoJsonSource),
+    Vector(VectorTileSource),
+    /// `raster`, `raster-dem` and `raster-array` sources.
+    Raster(RasterTileSource),
+    Image(ImageSource),
+    Video(VideoSource),
+    /// Sources without a dedicated handle, e.g. `model` or custom sources.
+    Other(JsValue),
+}
+
+impl SourceHandle {
+    pub(crate) fn from_js(source: JsValue) -> SourceHandle {
+        let type_ = js_sys::Reflect::get(&source, &JsValue::from("type"))
+            .ok()
+            .and_then(|t| t.as_string());
+
+        match type_.as_deref() {
+            Some("geojson") => SourceHandle::GeoJson(GeoJsonSource {
+                inner: source.unchecked_into(),
+            }),
+            Some("vector") => SourceHandle::Vector(VectorTileSource {
+                inner: source.unchecked_into(),
+            }),
+            Some("raster" | "raster-dem" | "raster-array") => {
+                SourceHandle::Raster(RasterTileSource {
+                    inner: source.unchecked_into(),
+                })
+            }
+            Some("image") => SourceHandle::Image(ImageSource {
+                inner: source.unchecked_into(),
+            }),
+            Some("video") => SourceHandle::Video(VideoSource {
+                inner: source.unchecked_into(),
+            }),
+            _ => SourceHandle::Other(source),
+        }
+    }
+}
+
+fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue> {
+    let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    Ok(value.serialize(&ser)?)
+}
+
+/// Turns a mapbox-gl-js API taking a node-style `(error, result)` callback into a future.
+async fn callback_future(call: impl FnOnce(&js_sys::Function)) -> Result<JsValue> {
+    let mut call = Some(call);
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let callback = Closure::once_into_js(move |error: JsValue, result: JsValue| {
+            if error.is_null() || error.is_undefined() {
+                resolve.call1(&JsValue::NULL, &result)
+            } else {
+                reject.call1(&JsValue::NULL, &error)
+            }
+        });
+        if let Some(call) = call.take() {
+            call(callback.unchecked_ref());
+        }
+    });
+
+    JsFuture::from(promise).await.map_err(|e| {
+        let message = e
+            .dyn_ref::<js_sys::Error>()
+            .map(|e| String::from(e.message()))
+            .unwrap_or_else(|| format!("{e:?}"));
+        Error::JsError(message)
+    })
 }
 
-impl<S> GeoJsonSourceSpec<S>
-where
-    S: Serialize,
-{
-    pub fn new(data: S) -> GeoJsonSourceSpec<S> {
-        GeoJsonSourceSpec {
-            r#type: "geojson".into(),
-            data,
+/// Changes to the features of a GeoJSON source, keyed by feature id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoJsonSourceDiff {
+    /// Features whose id was not in the source yet.
+    pub add: Vec<Feature>,
+    /// Replacements for features already in the source, matched by id.
+    pub update: Vec<Feature>,
+    /// Ids of features to drop from the source.
+    pub remove: Vec<Id>,
+}
+
+/// `Id` is neither `Eq` nor `Hash`, so ids are indexed by a tagged string instead.
+/// Integral numbers are keyed the same whether they were parsed as `1` or `1.0`.
+fn id_key(id: &Id) -> String {
+    match id {
+        Id::String(s) => format!("s:{s}"),
+        Id::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                format!("n:{i}")
+            } else if let Some(u) = n.as_u64() {
+                format!("n:{u}")
+            } else {
+                match n.as_f64() {
+                    Some(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => {
+                        format!("n:{}", f as i64)
+                    }
+                    _ => format!("n:{n}"),
+                }
+            }
         }
     }
 }
 
+fn index_by_id(collection: &FeatureCollection) -> Result<HashMap<String, &Feature>> {
+    collection
+        .features
+        .iter()
+        .map(|feature| match &feature.id {
+            Some(id) => Ok((id_key(id), feature)),
+            None => Err(Error::BadGeoJson(
+                "every feature needs an id to compute a diff".into(),
+            )),
+        })
+        .collect()
+}
+
+impl GeoJsonSourceDiff {
+    /// Computes the changes turning `old` into `new`. All features must have an `id`.
+    pub fn between(old: &FeatureCollection, new: &FeatureCollection) -> Result<GeoJsonSourceDiff> {
+        let old_features = index_by_id(old)?;
+        let new_features = index_by_id(new)?;
+
+        let mut diff = GeoJsonSourceDiff::default();
+        for feature in &new.features {
+            let key = feature.id.as_ref().map(id_key).unwrap_or_default();
+            match old_features.get(&key) {
+                None => diff.add.push(feature.clone()),
+                Some(&previous) if previous != feature => diff.update.push(feature.clone()),
+                Some(_) => {}
+            }
+        }
+        for feature in &old.features {
+            if let Some(id) = &feature.id {
+                if !new_features.contains_key(&id_key(id)) {
+                    diff.remove.push(id.clone());
+                }
+            }
+        }
+
+        Ok(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.update.is_empty() && self.remove.is_empty()
+    }
+
+    /// mapbox-gl-js `updateData` diff. Removals are applied before additions, so updated
+    /// features are sent as a removal of the old feature plus an addition of the new one.
+    fn to_update(&self) -> SourceUpdate<'_> {
+        SourceUpdate {
+            remove: self
+                .remove
+                .iter()
+                .chain(self.update.iter().filter_map(|f| f.id.as_ref()))
+                .collect(),
+            add: self.add.iter().chain(&self.update).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SourceUpdate<'a> {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remove: Vec<&'a Id>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    add: Vec<&'a Feature>,
+}
+
 pub struct GeoJsonSource {
     pub inner: crate::js::GeoJSONSource,
 }
@@ -35,4 +192,215 @@ impl GeoJsonSource {
         self.inner.GeoJSONSource_setData(data);
         Ok(())
     }
+
+    /// Ships only the changed features to the source instead of the whole data.
+    ///
+    /// The source has to be created with `dynamic: true`.
+    pub fn update_data(&mut self, diff: &GeoJsonSourceDiff) -> Result<()> {
+        if diff.is_empty() {
+            return Ok(());
+        }
+
+        self.inner
+            .GeoJSONSource_updateData(&to_js(&diff.to_update())?);
+
+        Ok(())
+    }
+
+    /// Zoom level at which the cluster expands into several children.
+    pub async fn get_cluster_expansion_zoom(&self, cluster_id: u64) -> Result<f64> {
+        let zoom = callback_future(|cb| {
+            self.inner
+                .GeoJSONSource_getClusterExpansionZoom(cluster_id as f64, cb)
+        })
+        .await?;
+
+        zoom.as_f64()
+            .ok_or_else(|| Error::BadEventFormat("cluster expansion zoom", format!("{zoom:?}")))
+    }
+
+    /// Children of a cluster on the next zoom level, clusters or points.
+    pub async fn get_cluster_children(&self, cluster_id: u64) -> Result<Vec<geojson::Feature>> {
+        let features = callback_future(|cb| {
+            self.inner
+                .GeoJSONSource_getClusterChildren(cluster_id as f64, cb)
+        })
+        .await?;
+
+        crate::feature::features_from_js(features)
+    }
+
+    /// Original points of a cluster, paginated with `limit` and `offset`.
+    pub async fn get_cluster_leaves(
+        &self,
+        cluster_id: u64,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<geojson::Feature>> {
+        let features = callback_future(|cb| {
+            self.inner.GeoJSONSource_getClusterLeaves(
+                cluster_id as f64,
+                limit as f64,
+                offset as f64,
+                cb,
+            )
+        })
+        .await?;
+
+        crate::feature::features_from_js(features)
+    }
+}
+
+/// https://docs.mapbox.com/mapbox-gl-js/api/sources/#vectortilesource
+pub struct VectorTileSource {
+    pub inner: crate::js::VectorTileSource,
+}
+
+impl VectorTileSource {
+    pub fn set_tiles(&mut self, tiles: &[String]) -> Result<()> {
+        self.inner.VectorTileSource_setTiles(to_js(tiles)?);
+        Ok(())
+    }
+    pub fn set_url(&mut self, url: impl Into<String>) {
+        self.inner.VectorTileSource_setUrl(url.into());
+    }
+}
+
+/// https://docs.mapbox.com/mapbox-gl-js/api/sources/#rastertilesource
+pub struct RasterTileSource {
+    pub inner: crate::js::RasterTileSource,
+}
+
+impl RasterTileSource {
+    pub fn set_tiles(&mut self, tiles: &[String]) -> Result<()> {
+        self.inner.RasterTileSource_setTiles(to_js(tiles)?);
+        Ok(())
+    }
+    pub fn set_url(&mut self, url: impl Into<String>) {
+        self.inner.RasterTileSource_setUrl(url.into());
+    }
+}
+
+#[derive(Serialize)]
+struct UpdateImageOptions {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    coordinates: Option<Coordinates>,
+}
+
+/// https://docs.mapbox.com/mapbox-gl-js/api/sources/#imagesource
+pub struct ImageSource {
+    pub inner: crate::js::ImageSource,
+}
+
+impl ImageSource {
+    /// Replaces the image, optionally moving it to new `coordinates` at the same time.
+    pub fn update_image(
+        &mut self,
+        url: impl Into<String>,
+        coordinates: Option<Coordinates>,
+    ) -> Result<()> {
+        let options = UpdateImageOptions {
+            url: url.into(),
+            coordinates,
+        };
+        self.inner.ImageSource_updateImage(to_js(&options)?);
+        Ok(())
+    }
+    pub fn set_coordinates(&mut self, coordinates: Coordinates) -> Result<()> {
+        self.inner.ImageSource_setCoordinates(to_js(&coordinates)?);
+        Ok(())
+    }
+}
+
+/// https://docs.mapbox.com/mapbox-gl-js/api/sources/#videosource
+pub struct VideoSource {
+    pub inner: crate::js::VideoSource,
+}
+
+impl VideoSource {
+    pub fn play(&mut self) {
+        self.inner.VideoSource_play();
+    }
+    pub fn pause(&mut self) {
+        self.inner.VideoSource_pause();
+    }
+    pub fn set_coordinates(&mut self, coordinates: Coordinates) -> Result<()> {
+        self.inner.VideoSource_setCoordinates(to_js(&coordinates)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn feature(id: serde_json::Value, name: &str) -> Feature {
+        serde_json::from_value(json!({
+            "type": "Feature",
+            "id": id,
+            "geometry": null,
+            "properties": { "name": name },
+        }))
+        .unwrap()
+    }
+
+    fn collection(features: Vec<Feature>) -> FeatureCollection {
+        FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: None,
+        }
+    }
+
+    #[test]
+    fn between() {
+        let old = collection(vec![
+            feature(json!(1), "kept"),
+            feature(json!("a"), "before"),
+            feature(json!(2), "removed"),
+        ]);
+        let new = collection(vec![
+            feature(json!(1), "kept"),
+            feature(json!("a"), "after"),
+            feature(json!(3), "added"),
+        ]);
+
+        let diff = GeoJsonSourceDiff::between(&old, &new).unwrap();
+        assert_eq!(diff.add, vec![feature(json!(3), "added")]);
+        assert_eq!(diff.update, vec![feature(json!("a"), "after")]);
+        assert_eq!(diff.remove, vec![Id::Number(2.into())]);
+    }
+
+    #[test]
+    fn between_normalizes_numeric_ids() {
+        let old = collection(vec![feature(json!(1), "same")]);
+        let new = collection(vec![feature(json!(1.0), "same")]);
+
+        let diff = GeoJsonSourceDiff::between(&old, &new).unwrap();
+        assert!(diff.add.is_empty());
+        assert!(diff.remove.is_empty());
+    }
+
+    #[test]
+    fn between_requires_ids() {
+        let mut no_id = feature(json!(1), "no id");
+        no_id.id = None;
+        let res = GeoJsonSourceDiff::between(&collection(vec![]), &collection(vec![no_id]));
+        assert!(matches!(res, Err(Error::BadGeoJson(_))));
+    }
+
+    #[test]
+    fn update_payload() {
+        let diff = GeoJsonSourceDiff {
+            add: vec![feature(json!(3), "added")],
+            update: vec![feature(json!("a"), "after")],
+            remove: vec![Id::Number(2.into())],
+        };
+        let payload = serde_json::to_value(diff.to_update()).unwrap();
+        assert_eq!(payload["remove"], json!([2, "a"]));
+        assert_eq!(payload["add"][0]["id"], json!(3));
+        assert_eq!(payload["add"][1]["id"], json!("a"));
+    }
 }
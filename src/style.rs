@@ -1,4 +1,4 @@
-use crate::layer::Layer;
+use crate::layer::{Expression, Layer};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -58,7 +58,7 @@ pub struct Style {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sprite: Option<String>,
+    pub sprite: Option<Sprite>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub glyphs: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -83,8 +83,31 @@ pub struct Style {
     pub visibility: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub draft: Option<bool>,
-    // TODO: flesh out optional properties of Style spec
-    //       see https://docs.mapbox.com/style-spec/reference/root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imports: Option<Vec<Import>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<HashMap<String, SchemaOption>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub light: Option<Light>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lights: Option<Vec<Light3d>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terrain: Option<Terrain>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fog: Option<Fog>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<Camera>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projection: Option<Projection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transition: Option<Transition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub models: Option<HashMap<String, String>>,
+    /// Properties not modeled above, kept so that a style round-trips without losing anything.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl From<Style> for JsValue {
@@ -118,20 +141,255 @@ impl FromWasmAbi for Style {
     }
 }
 
-pub type Sources = HashMap<String, Source>;
+/// Sprite of a style, either a single URL or a list of named sprites.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Sprite {
+    Url(String),
+    Multiple(Vec<NamedSprite>),
+}
 
+impl From<String> for Sprite {
+    fn from(url: String) -> Self {
+        Sprite::Url(url)
+    }
+}
+
+impl From<&str> for Sprite {
+    fn from(url: &str) -> Self {
+        Sprite::Url(url.into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NamedSprite {
+    pub id: String,
+    pub url: String,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/imports
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct Source {
+pub struct Import {
+    pub id: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<HashMap<String, Expression<()>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Box<Style>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Configuration option of a style fragment.
+/// https://docs.mapbox.com/style-spec/reference/root/#schema
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaOption {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_value: Option<f64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Global light of a style (the legacy single light).
+/// https://docs.mapbox.com/style-spec/reference/light
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Light {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intensity: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Ambient, directional or flat light of a 3D style.
+/// https://docs.mapbox.com/style-spec/reference/lights
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Light3d {
+    pub id: String,
     pub r#type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tiles: Option<Vec<String>>,
+    pub properties: Option<HashMap<String, Expression<()>>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/terrain
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Terrain {
+    pub source: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub url: Option<String>,
+    pub exaggeration: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/fog
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Fog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_color: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub space_color: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizon_blend: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub star_intensity: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_range: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+/// https://docs.mapbox.com/style-spec/reference/camera
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Camera {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_projection: Option<Expression<()>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/projection
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Projection {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallels: Option<[f64; 2]>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/transition
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Transition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<f64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+pub type Sources = HashMap<String, Source>;
+
+/// Source of a style.
+/// https://docs.mapbox.com/style-spec/reference/sources
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+#[allow(clippy::large_enum_variant)]
+pub enum Source {
+    Vector(VectorSourceSpec),
+    Raster(RasterSourceSpec),
+    RasterDem(RasterDemSourceSpec),
+    RasterArray(RasterArraySourceSpec),
+    #[serde(rename = "geojson")]
+    GeoJson(GeoJsonSourceSpec),
+    Image(ImageSourceSpec),
+    Video(VideoSourceSpec),
+    Model(ModelSourceSpec),
+    /// Source of a type not modeled by this crate, e.g. `canvas`, kept as it is.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl<'de> Deserialize<'de> for Source {
+    fn deserialize<D>(de: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        use serde_json::Value;
+
+        let mut source = serde_json::Map::deserialize(de)?;
+        let r#type = match source.remove("type") {
+            Some(Value::String(r#type)) => r#type,
+            Some(other) => return Err(D::Error::custom(format!("invalid source type {other}"))),
+            None => return Err(D::Error::missing_field("type")),
+        };
+
+        macro_rules! known {
+            ($($name:literal => $variant:ident),* $(,)?) => {
+                match r#type.as_str() {
+                    $($name => serde_json::from_value(Value::Object(source)).map(Source::$variant),)*
+                    _ => {
+                        source.insert("type".into(), Value::String(r#type));
+                        Ok(Source::Other(Value::Object(source)))
+                    }
+                }
+            };
+        }
+
+        known!(
+            "vector" => Vector,
+            "raster" => Raster,
+            "raster-dem" => RasterDem,
+            "raster-array" => RasterArray,
+            "geojson" => GeoJson,
+            "image" => Image,
+            "video" => Video,
+            "model" => Model,
+        )
+        .map_err(D::Error::custom)
+    }
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Vector(VectorSourceSpec::default())
+    }
+}
+
+macro_rules! impl_from_spec {
+    ($($variant:ident($spec:ty)),* $(,)?) => {
+        $(
+            impl From<$spec> for Source {
+                fn from(spec: $spec) -> Self {
+                    Source::$variant(spec)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_spec!(
+    Vector(VectorSourceSpec),
+    Raster(RasterSourceSpec),
+    RasterDem(RasterDemSourceSpec),
+    RasterArray(RasterArraySourceSpec),
+    GeoJson(GeoJsonSourceSpec),
+    Image(ImageSourceSpec),
+    Video(VideoSourceSpec),
+    Model(ModelSourceSpec),
+);
+
 impl From<Source> for JsValue {
     fn from(val: Source) -> Self {
-        serde_wasm_bindgen::to_value(&val).unwrap()
+        val.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap()
     }
 }
 
@@ -145,7 +403,9 @@ impl IntoWasmAbi for Source {
     type Abi = <JsValue as IntoWasmAbi>::Abi;
 
     fn into_abi(self) -> Self::Abi {
-        serde_wasm_bindgen::to_value(&self).unwrap().into_abi()
+        self.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap()
+            .into_abi()
     }
 }
 
@@ -157,6 +417,308 @@ impl FromWasmAbi for Source {
     }
 }
 
+/// Tiling scheme of a tiled source.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    #[default]
+    Xyz,
+    Tms,
+}
+
+/// Encoding of a raster-dem source.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DemEncoding {
+    #[default]
+    Mapbox,
+    Terrarium,
+}
+
+/// Feature property to use as a feature id, either for all layers or per source layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PromoteId {
+    Property(String),
+    PerLayer(HashMap<String, String>),
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#vector
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorSourceSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<[f64; 4]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<Scheme>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promote_id: Option<PromoteId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatile: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl VectorSourceSpec {
+    pub fn new(url: impl Into<String>) -> VectorSourceSpec {
+        VectorSourceSpec {
+            url: Some(url.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#raster
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RasterSourceSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<[f64; 4]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<Scheme>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatile: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#raster-dem
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RasterDemSourceSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<[f64; 4]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<DemEncoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatile: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#raster-array
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RasterArraySourceSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<[f64; 4]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raster_layers: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatile: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Data of a GeoJSON source, either a URL or inline GeoJSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GeoJsonData {
+    Url(String),
+    GeoJson(geojson::GeoJson),
+}
+
+impl From<String> for GeoJsonData {
+    fn from(url: String) -> Self {
+        GeoJsonData::Url(url)
+    }
+}
+
+impl From<&str> for GeoJsonData {
+    fn from(url: &str) -> Self {
+        GeoJsonData::Url(url.into())
+    }
+}
+
+impl From<geojson::GeoJson> for GeoJsonData {
+    fn from(data: geojson::GeoJson) -> Self {
+        GeoJsonData::GeoJson(data)
+    }
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#geojson
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonSourceSpec {
+    pub data: GeoJsonData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expression<()>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_radius: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_max_zoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_min_points: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_properties: Option<HashMap<String, Expression<()>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_metrics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generate_id: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promote_id: Option<PromoteId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl GeoJsonSourceSpec {
+    pub fn new(data: impl Into<GeoJsonData>) -> GeoJsonSourceSpec {
+        GeoJsonSourceSpec {
+            data: data.into(),
+            minzoom: None,
+            maxzoom: None,
+            attribution: None,
+            buffer: None,
+            filter: None,
+            tolerance: None,
+            cluster: None,
+            cluster_radius: None,
+            cluster_max_zoom: None,
+            cluster_min_points: None,
+            cluster_properties: None,
+            line_metrics: None,
+            generate_id: None,
+            promote_id: None,
+            dynamic: None,
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Corners of an image or video source in clockwise order, starting at the top left.
+pub type Coordinates = [[f64; 2]; 4];
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#image
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageSourceSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub coordinates: Coordinates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ImageSourceSpec {
+    pub fn new(url: impl Into<String>, coordinates: Coordinates) -> ImageSourceSpec {
+        ImageSourceSpec {
+            url: Some(url.into()),
+            coordinates,
+            extra: Default::default(),
+        }
+    }
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#video
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoSourceSpec {
+    pub urls: Vec<String>,
+    pub coordinates: Coordinates,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl VideoSourceSpec {
+    pub fn new(urls: Vec<String>, coordinates: Coordinates) -> VideoSourceSpec {
+        VideoSourceSpec {
+            urls,
+            coordinates,
+            extra: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSpec {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<[f64; 3]>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://docs.mapbox.com/style-spec/reference/sources/#model
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSourceSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxzoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub models: Option<HashMap<String, ModelSpec>>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
 #[derive(Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct StyleOptions {
@@ -174,19 +736,29 @@ impl StyleOptions {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MapboxSdkSupport {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub js: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub android: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ios: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metadata {
-    #[serde(rename = "mapbox:origin")]
+    #[serde(rename = "mapbox:origin", skip_serializing_if = "Option::is_none")]
     pub mapbox_origin: Option<String>,
-    #[serde(rename = "mapbox:autocomposite")]
+    #[serde(
+        rename = "mapbox:autocomposite",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mapbox_autocomposite: Option<bool>,
-    #[serde(rename = "mapbox:type")]
+    #[serde(rename = "mapbox:type", skip_serializing_if = "Option::is_none")]
     pub mapbox_type: Option<String>,
-    #[serde(rename = "mapbox:sdk-support")]
+    #[serde(rename = "mapbox:sdk-support", skip_serializing_if = "Option::is_none")]
     pub mapbox_sdk_support: Option<MapboxSdkSupport>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
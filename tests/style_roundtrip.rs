@@ -0,0 +1,92 @@
+//! Every style in `tests/styles` must survive `serde_json` -> `Style` -> `serde_json` unchanged.
+//!
+//! Styles exported from Mapbox Studio or MapLibre go in `tests/styles/exported` and must
+//! round-trip exactly, apart from how numbers are written.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use mapboxgl::{layer::Layer, Source, Style};
+use serde_json::Value;
+
+/// Mapbox GL does not distinguish `1` from `1.0`, but the typed structs store numbers as `f64`.
+fn normalize_numbers(value: Value) -> Value {
+    match value {
+        Value::Number(n) => n
+            .as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map_or(Value::Number(n), Value::Number),
+        Value::Array(list) => Value::Array(list.into_iter().map(normalize_numbers).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, normalize_numbers(v)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Also leaves out empty `layout`/`paint`, which the typed structs don't write back.
+fn normalize(value: Value) -> Value {
+    match normalize_numbers(value) {
+        Value::Array(list) => Value::Array(list.into_iter().map(normalize).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(k, v)| {
+                    !((k == "layout" || k == "paint")
+                        && v.as_object().is_some_and(|o| o.is_empty()))
+                })
+                .map(|(k, v)| (k, normalize(v)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn round_trip(dir: &str, normalize: fn(Value) -> Value) -> Result<()> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(dir);
+    let mut count = 0;
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let original: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        let style: Style = serde_json::from_value(original.clone())
+            .with_context(|| format!("deserializing {}", path.display()))?;
+        let written = serde_json::to_value(&style)?;
+        assert_eq!(
+            normalize(original),
+            normalize(written),
+            "{} did not round-trip",
+            path.display()
+        );
+        count += 1;
+    }
+    assert!(count > 0, "no styles found in {}", dir.display());
+    Ok(())
+}
+
+#[test]
+fn styles_round_trip() -> Result<()> {
+    round_trip("tests/styles", normalize)
+}
+
+#[test]
+fn exported_styles_round_trip_exactly() -> Result<()> {
+    round_trip("tests/styles/exported", normalize_numbers)
+}
+
+#[test]
+fn unknown_types_are_kept() -> Result<()> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/styles/unknown-types.json");
+    let style: Style = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    assert!(matches!(style.sources["composite"], Source::Vector(_)));
+    assert!(matches!(style.sources["paint"], Source::Other(_)));
+    assert!(matches!(style.sources["tracks"], Source::Other(_)));
+    assert!(matches!(style.layers[0], Layer::Raster(_)));
+    assert!(matches!(style.layers[1], Layer::Other(_)));
+    assert!(matches!(style.layers[2], Layer::Line(_)));
+    Ok(())
+}